@@ -0,0 +1,305 @@
+use crate::cli::{BatchCommand, BatchOperation, Cli, Commands};
+use crate::client_request::{get_client, read_user_password, run_server, stego_client_wrap_error};
+use crate::configuration::Settings;
+use crate::print_success;
+use crate::retry::backoff_delay;
+use color_eyre::eyre::eyre;
+use color_eyre::{Result, Section};
+use colored::Colorize;
+use futures::stream::{self, StreamExt};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use stego_wave::object::StegoWaveClient;
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+
+/// Runs `batch.operation` against every file under `batch.input_dir` matching
+/// `batch.pattern`, spreading the work across `batch.concurrency` workers, each
+/// with its own [`StegoWaveClient`] connection so files are genuinely processed
+/// in parallel. A file's error never aborts the run; it's collected and
+/// reported in the final summary instead.
+pub async fn batch_command(cli: &Cli, settings: &Settings) -> Result<()> {
+    let Commands::Batch(batch) = cli.command() else {
+        unreachable!("batch_command is only called for Commands::Batch")
+    };
+
+    let files = discover_files(&batch.input_dir, &batch.pattern)?;
+    if files.is_empty() {
+        return Err(eyre!(
+            "No files matched pattern '{}' under {}",
+            batch.pattern,
+            batch.input_dir.display()
+        ));
+    }
+
+    let password = read_user_password()?;
+    let concurrency = batch.concurrency.max(1);
+    let worker_count = concurrency.min(files.len());
+    let client_pool = Arc::new(Mutex::new(
+        connect_pool(cli, batch, settings, worker_count).await?,
+    ));
+
+    let multi = MultiProgress::new();
+    let overall = multi.add(ProgressBar::new(files.len() as u64));
+    overall.set_style(
+        ProgressStyle::with_template("{msg} [{bar:40.cyan/blue}] {pos}/{len}")
+            .unwrap_or_else(|_| ProgressStyle::default_bar())
+            .progress_chars("=> "),
+    );
+    overall.set_message("batch");
+
+    let worker_bars: Vec<ProgressBar> = (0..worker_count)
+        .map(|worker| {
+            let bar = multi.add(ProgressBar::new_spinner());
+            bar.set_prefix(format!("worker {worker}"));
+            bar.set_style(
+                ProgressStyle::with_template("  {prefix}: {msg}")
+                    .unwrap_or_else(|_| ProgressStyle::default_spinner()),
+            );
+            bar
+        })
+        .collect();
+    let worker_bars = Arc::new(worker_bars);
+
+    let results: Vec<(PathBuf, Result<PathBuf, String>)> = stream::iter(files)
+        .map(|source| {
+            let client_pool = Arc::clone(&client_pool);
+            let worker_bars = Arc::clone(&worker_bars);
+            let overall = overall.clone();
+            let password = password.clone();
+
+            async move {
+                // Only ever contended for the instant it takes to pop/push a
+                // (worker, client) pair, never across the network round-trip
+                // `process_one_file` makes with the client it borrows: each
+                // worker owns its own connection, so requests run truly
+                // concurrently instead of serializing behind one shared client.
+                let worker = client_pool.lock().await.pop();
+                if let Some((worker, mut client)) = worker {
+                    worker_bars[worker].set_message(source.display().to_string());
+
+                    let result =
+                        process_one_file(&mut client, batch, &password, &source).await;
+
+                    worker_bars[worker].set_message("idle");
+                    client_pool.lock().await.push((worker, client));
+                    overall.inc(1);
+
+                    (source, result)
+                } else {
+                    overall.inc(1);
+                    (source, Err("no worker connection available".to_string()))
+                }
+            }
+        })
+        .buffer_unordered(concurrency)
+        .collect()
+        .await;
+
+    overall.finish_with_message("batch done");
+    for bar in worker_bars.iter() {
+        bar.finish_and_clear();
+    }
+
+    let succeeded = results.iter().filter(|(_, result)| result.is_ok()).count();
+    let failed = results.len() - succeeded;
+
+    for (source, result) in &results {
+        if let Err(message) = result {
+            eprintln!(
+                "{} {}: {}",
+                "[FAILED]".red().bold(),
+                source.display().to_string().white().bold(),
+                message
+            );
+        }
+    }
+
+    print_success!(batch: succeeded, failed, results.len());
+
+    if failed > 0 && succeeded == 0 {
+        return Err(eyre!("All {failed} file(s) in the batch failed"));
+    }
+
+    Ok(())
+}
+
+/// Connects `worker_count` independent client connections, one per pool slot, so
+/// each concurrent worker makes its network round-trips on its own connection
+/// instead of serializing every file behind a single shared one. The first
+/// connection goes through [`connect`] (which auto-starts the server if
+/// configured); the rest use a plain [`get_client`] now that the server is
+/// known to be reachable.
+async fn connect_pool(
+    cli: &Cli,
+    batch: &BatchCommand,
+    settings: &Settings,
+    worker_count: usize,
+) -> Result<Vec<(usize, Box<dyn StegoWaveClient>)>> {
+    let mut pool = Vec::with_capacity(worker_count);
+    pool.push((0, connect(cli, batch, settings).await?));
+
+    for worker in 1..worker_count {
+        let client = get_client(&batch.command.server, settings)
+            .await
+            .map_err(stego_client_wrap_error)?;
+        pool.push((worker, client));
+    }
+
+    Ok(pool)
+}
+
+/// Connects once up front, auto-starting the server and retrying with the
+/// configured backoff policy on a "Connection failed", the same way the
+/// single-file commands do in `client_request`.
+async fn connect(
+    cli: &Cli,
+    batch: &BatchCommand,
+    settings: &Settings,
+) -> Result<Box<dyn StegoWaveClient>> {
+    match get_client(&batch.command.server, settings).await {
+        Ok(client) => Ok(client),
+        Err(err) if err.to_string() == "Connection failed" => {
+            if !batch.command.start_server {
+                return Err(eyre!("Failed to connect to the servers.").suggestion(
+                    "Try using the '--start-server' flag and the program will start the server automatically",
+                ));
+            }
+
+            run_server(cli, settings).await?;
+
+            let retry = &settings.retry;
+            for attempt in 0..retry.max_attempts {
+                match get_client(&batch.command.server, settings).await {
+                    Ok(client) => return Ok(client),
+                    Err(err) if err.to_string() == "Connection failed" => {
+                        sleep(backoff_delay(retry, attempt as u32)).await;
+                    }
+                    Err(err) => return Err(stego_client_wrap_error(err)),
+                }
+            }
+
+            Err(eyre!("Failed to connect to the servers after retrying."))
+        }
+        Err(err) => Err(stego_client_wrap_error(err)),
+    }
+}
+
+/// Walks `input_dir` and keeps the entries (relative to it) matching `pattern`.
+fn discover_files(input_dir: &Path, pattern: &str) -> Result<Vec<PathBuf>> {
+    let full_pattern = input_dir.join(pattern);
+    let full_pattern = full_pattern
+        .to_str()
+        .ok_or_else(|| eyre!("--input_dir contains non-UTF-8 characters"))?
+        .to_string();
+
+    let mut files: Vec<PathBuf> = glob::glob(&full_pattern)?
+        .filter_map(|entry| entry.ok())
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+
+    Ok(files)
+}
+
+async fn process_one_file(
+    client: &mut Box<dyn StegoWaveClient>,
+    batch: &BatchCommand,
+    password: &str,
+    source: &Path,
+) -> Result<PathBuf, String> {
+    let file_bytes = tokio::fs::read(source)
+        .await
+        .map_err(|err| format!("failed to read file: {err}"))?;
+    let (format, lsb_deep) = batch.command.resolve_format_and_lsb_deep();
+    let format: String = format.into();
+
+    match &batch.operation {
+        BatchOperation::Hide(hide) => {
+            let result = client
+                .hide_message(
+                    file_bytes,
+                    hide.message.clone(),
+                    password.to_string(),
+                    format,
+                    lsb_deep,
+                    batch.command.compress,
+                    batch.command.encrypt,
+                )
+                .await
+                .map_err(|err| err.to_string())?;
+
+            write_result(source, &batch.output_dir, "hidden", &result).await
+        }
+        BatchOperation::Clear => {
+            let result = client
+                .clear_message(file_bytes, password.to_string(), format, lsb_deep)
+                .await
+                .map_err(|err| err.to_string())?;
+
+            write_result(source, &batch.output_dir, "cleared", &result).await
+        }
+        BatchOperation::Extract => {
+            let message = client
+                .extract_message(file_bytes, password.to_string(), format, lsb_deep)
+                .await
+                .map_err(|err| err.to_string())?;
+
+            write_result(source, &batch.output_dir, "extracted", message.as_bytes()).await
+        }
+    }
+}
+
+/// Writes `bytes` next to `source` (suffixed with `label`) or, when `output_dir` is
+/// set, into that directory under the source's original file name. Extracted
+/// messages reuse this too, landing in a `.txt` file alongside/instead of the
+/// audio so a batch of `extract` runs doesn't print hundreds of messages to stdout.
+async fn write_result(
+    source: &Path,
+    output_dir: &Option<PathBuf>,
+    label: &str,
+    bytes: &[u8],
+) -> Result<PathBuf, String> {
+    let is_message = label == "extracted";
+    let destination = match output_dir {
+        Some(dir) => {
+            let file_name = source
+                .file_name()
+                .ok_or_else(|| "source file has no file name".to_string())?;
+            let mut path = dir.join(file_name);
+            if is_message {
+                path.set_extension("txt");
+            }
+            path
+        }
+        None => {
+            let stem = source
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .ok_or_else(|| "source file has no file name".to_string())?;
+            let extension = if is_message {
+                "txt".to_string()
+            } else {
+                source
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or("out")
+                    .to_string()
+            };
+            source.with_file_name(format!("{stem}.{label}.{extension}"))
+        }
+    };
+
+    if let Some(parent) = destination.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|err| format!("failed to create output directory: {err}"))?;
+    }
+
+    tokio::fs::write(&destination, bytes)
+        .await
+        .map_err(|err| format!("failed to write result: {err}"))?;
+
+    Ok(destination)
+}
@@ -23,6 +23,8 @@ impl Cli {
             Commands::Hide(hide) => hide.command.server.clone(),
             Commands::Extract(extract) => extract.command.server.clone(),
             Commands::Clear(clear) => clear.command.server.clone(),
+            Commands::Capacity(capacity) => capacity.command.server.clone(),
+            Commands::Batch(batch) => batch.command.server.clone(),
         }
     }
 
@@ -31,6 +33,8 @@ impl Cli {
             Commands::Hide(hide) => hide.command.start_server,
             Commands::Extract(extract) => extract.command.start_server,
             Commands::Clear(clear) => clear.command.start_server,
+            Commands::Capacity(capacity) => capacity.command.start_server,
+            Commands::Batch(batch) => batch.command.start_server,
         }
     }
     pub fn get_file_config(&self) -> &str {
@@ -38,6 +42,8 @@ impl Cli {
             Commands::Hide(hide) => &hide.command.config,
             Commands::Extract(extract) => &extract.command.config,
             Commands::Clear(clear) => &clear.command.config,
+            Commands::Capacity(capacity) => &capacity.command.config,
+            Commands::Batch(batch) => &batch.command.config,
         }
     }
 }
@@ -50,6 +56,10 @@ pub enum Commands {
     Extract(ExtractCommand),
     #[command(about = "Clear the hidden secret message from an audio file")]
     Clear(ClearCommand),
+    #[command(about = "Reports the spare message capacity of an audio file without hiding anything")]
+    Capacity(CapacityCommand),
+    #[command(about = "Applies Hide/Extract/Clear to every audio file under a directory")]
+    Batch(BatchCommand),
 }
 
 #[allow(clippy::upper_case_acronyms)]
@@ -57,18 +67,109 @@ pub enum Commands {
 pub enum StegoWaveServer {
     GRPC,
     REST,
+    WebSocket,
     Auto,
 }
 
 #[derive(Debug, Clone, ValueEnum)]
 pub enum StegoWaveFormat {
+    WAV8,
     WAV16,
+    WAV24,
+    WAV32,
+    WAV32F,
+    FLAC,
 }
 
 impl From<StegoWaveFormat> for String {
     fn from(value: StegoWaveFormat) -> Self {
         match value {
+            StegoWaveFormat::WAV8 => "wav8".to_string(),
             StegoWaveFormat::WAV16 => "wav16".to_string(),
+            StegoWaveFormat::WAV24 => "wav24".to_string(),
+            StegoWaveFormat::WAV32 => "wav32".to_string(),
+            StegoWaveFormat::WAV32F => "wav32f".to_string(),
+            StegoWaveFormat::FLAC => "flac".to_string(),
+        }
+    }
+}
+
+/// The highest `lsb_deep` each format's own encoder will accept, mirroring the
+/// per-format limits enforced in `stego_wave::formats` (`WAV32F`'s mantissa-bit
+/// cap is the tightest; `WAV32`/`FLAC` can go as deep as their sample width).
+/// Kept here purely to pick sensible [`QualityPreset`] defaults client-side —
+/// the server is still the source of truth and will reject anything over this.
+fn max_lsb_deep_for(format: &StegoWaveFormat) -> u8 {
+    match format {
+        StegoWaveFormat::WAV8 => 8,
+        StegoWaveFormat::WAV16 => 16,
+        StegoWaveFormat::WAV24 => 24,
+        StegoWaveFormat::WAV32 => 32,
+        StegoWaveFormat::WAV32F => 8,
+        StegoWaveFormat::FLAC => 32,
+    }
+}
+
+/// A casual-user-facing shortcut for the capacity/audibility tradeoff that
+/// `format` + `lsb_deep` otherwise require picking by hand.
+#[derive(Debug, Clone, ValueEnum)]
+pub enum QualityPreset {
+    #[value(name = "max-capacity")]
+    MaxCapacity,
+    Balanced,
+    Stealth,
+}
+
+impl QualityPreset {
+    /// This preset's target `lsb_deep`, from most- to least-aggressive.
+    fn target_lsb_deep(&self) -> u8 {
+        match self {
+            QualityPreset::MaxCapacity => 24,
+            QualityPreset::Balanced => 8,
+            QualityPreset::Stealth => 1,
+        }
+    }
+
+    /// The next less-aggressive preset to fall back to when this one's target
+    /// depth doesn't fit the chosen format. `Stealth`'s target of 1 always fits
+    /// every format, so resolution always terminates.
+    fn next_tier(&self) -> Option<QualityPreset> {
+        match self {
+            QualityPreset::MaxCapacity => Some(QualityPreset::Balanced),
+            QualityPreset::Balanced => Some(QualityPreset::Stealth),
+            QualityPreset::Stealth => None,
+        }
+    }
+
+    /// The format to assume when the user picked a preset but no explicit
+    /// `--format`: the highest-capacity integer format for `MaxCapacity`, the
+    /// lossless FLAC codec for `Balanced`, and the finest-grained (float
+    /// mantissa bit) format for `Stealth`.
+    fn default_format(&self) -> StegoWaveFormat {
+        match self {
+            QualityPreset::MaxCapacity => StegoWaveFormat::WAV32,
+            QualityPreset::Balanced => StegoWaveFormat::FLAC,
+            QualityPreset::Stealth => StegoWaveFormat::WAV32F,
+        }
+    }
+
+    /// Expands this preset into a concrete `(format, lsb_deep)` pair, falling
+    /// back through less-aggressive tiers until one's target depth fits
+    /// `format` (or the preset's own [`Self::default_format`] if none was given).
+    pub fn resolve(&self, format: Option<StegoWaveFormat>) -> (StegoWaveFormat, u8) {
+        let format = format.unwrap_or_else(|| self.default_format());
+        let max_lsb_deep = max_lsb_deep_for(&format);
+
+        let mut tier = self.clone();
+        loop {
+            let target = tier.target_lsb_deep();
+            if target <= max_lsb_deep {
+                return (format, target);
+            }
+            match tier.next_tier() {
+                Some(next) => tier = next,
+                None => return (format, max_lsb_deep),
+            }
         }
     }
 }
@@ -85,9 +186,17 @@ pub struct CommonFields {
         value_enum,
         long = "format",
         short = 'f',
-        help = "Audio file format (e.g., wav16, ...) used for processing the file"
+        help = "Audio file format (e.g., wav8, wav16, wav24, wav32, wav32f, flac) used for processing the file. Required unless --preset is given"
+    )]
+    pub format: Option<StegoWaveFormat>,
+
+    #[arg(
+        value_enum,
+        long = "preset",
+        short = 'p',
+        help = "Quality preset bundling a sensible (format, lsb_deep) pair (max-capacity, balanced, stealth); an explicit --format/--lsb_deep always overrides the part of the preset it covers"
     )]
-    pub format: StegoWaveFormat,
+    pub preset: Option<QualityPreset>,
 
     #[arg(
         value_enum,
@@ -108,10 +217,9 @@ pub struct CommonFields {
     #[arg(
         long = "lsb_deep",
         short = 'l',
-        help = "Number of least significant bits to modify",
-        default_value_t = 1
+        help = "Number of least significant bits to modify. Defaults to a value picked by --preset (or the 'balanced' preset if neither is given)"
     )]
-    pub lsb_deep: u8,
+    pub lsb_deep: Option<u8>,
 
     #[arg(
         long = "config",
@@ -120,6 +228,37 @@ pub struct CommonFields {
         default_value = CONFIG_FILE,
     )]
     pub config: String,
+
+    #[arg(
+        long = "compress",
+        default_value_t = false,
+        help = "Compress the message before hiding it, to fit more under a given lsb_deep (wav16 only)"
+    )]
+    pub compress: bool,
+
+    #[arg(
+        long = "encrypt",
+        default_value_t = false,
+        help = "Encrypt the message with --password before hiding it, instead of embedding it as plaintext"
+    )]
+    pub encrypt: bool,
+}
+
+impl CommonFields {
+    /// Resolves this invocation's `(format, lsb_deep)`, letting an explicit
+    /// `--format`/`--lsb_deep` override whichever part of `--preset` it covers.
+    /// Falls back to the `Balanced` preset when neither a preset nor both
+    /// explicit values were given.
+    pub fn resolve_format_and_lsb_deep(&self) -> (StegoWaveFormat, u8) {
+        if let (Some(format), Some(lsb_deep)) = (&self.format, self.lsb_deep) {
+            return (format.clone(), lsb_deep);
+        }
+
+        let preset = self.preset.clone().unwrap_or(QualityPreset::Balanced);
+        let (resolved_format, resolved_lsb_deep) = preset.resolve(self.format.clone());
+
+        (resolved_format, self.lsb_deep.unwrap_or(resolved_lsb_deep))
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -158,3 +297,65 @@ pub struct ClearCommand {
     )]
     pub output_file: Option<PathBuf>,
 }
+
+#[derive(Debug, Parser)]
+pub struct CapacityCommand {
+    #[clap(flatten)]
+    pub command: CommonFields,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BatchOperation {
+    #[command(about = "Hides the same secret message in every matched file")]
+    Hide(BatchHideArgs),
+    #[command(about = "Extracts the hidden secret message from every matched file")]
+    Extract,
+    #[command(about = "Clears the hidden secret message from every matched file")]
+    Clear,
+}
+
+#[derive(Debug, Parser)]
+pub struct BatchHideArgs {
+    #[arg(
+        long = "message",
+        short = 'm',
+        help = "The secret message to hide inside every matched file"
+    )]
+    pub message: String,
+}
+
+#[derive(Debug, Parser)]
+pub struct BatchCommand {
+    #[command(subcommand)]
+    pub operation: BatchOperation,
+
+    #[clap(flatten)]
+    pub command: CommonFields,
+
+    #[arg(
+        long = "input_dir",
+        help = "Directory to scan for audio files matching --pattern (note: --input_file is ignored in batch mode)"
+    )]
+    pub input_dir: PathBuf,
+
+    #[arg(
+        long = "pattern",
+        default_value = "*",
+        help = "Glob pattern, relative to --input_dir, selecting which files to process"
+    )]
+    pub pattern: String,
+
+    #[arg(
+        long = "output_dir",
+        help = "Directory to write results into; defaults to writing each result next to its source file"
+    )]
+    pub output_dir: Option<PathBuf>,
+
+    #[arg(
+        long = "concurrency",
+        short = 'j',
+        default_value_t = 4,
+        help = "Maximum number of files processed at the same time"
+    )]
+    pub concurrency: usize,
+}
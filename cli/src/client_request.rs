@@ -1,49 +1,68 @@
-use crate::cli::{ClearCommand, Cli, Commands, ExtractCommand, HideCommand, StegoWaveServer};
+use crate::cli::{
+    CapacityCommand, ClearCommand, Cli, Commands, ExtractCommand, HideCommand, StegoWaveServer,
+};
 use crate::configuration::Settings;
 use crate::formating::print_success_helper;
 use crate::print_success;
+use crate::retry::backoff_delay;
 use color_eyre::eyre::eyre;
 use color_eyre::{Report, Result, Section};
 use colored::Colorize;
+use grpc_server::services::{PendingUploadBackend, PendingUploadStore};
 use std::io;
 use std::io::{Write, stderr};
 use std::net::SocketAddr;
 use std::net::TcpListener;
 use std::path::PathBuf;
-use std::time::Duration;
+use std::sync::Arc;
+use stego_wave::auth::TokenAuthority;
 use stego_wave::error::StegoWaveClientError;
+use stego_wave::metrics::install_recorder;
 use stego_wave::object::StegoWaveClient;
+use stego_wave::share::{ShareBackend, ShareStore};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::time::sleep;
 
-const QUERY_ATTEMPTS: u8 = 2;
-
 pub async fn client_request(cli: Cli, settings: Settings) -> Result<()> {
-    let password = read_user_password()?;
+    if matches!(cli.command(), Commands::Batch(_)) {
+        return crate::batch::batch_command(&cli, &settings).await;
+    }
+
+    // Capacity is a pure read-only query: no password is ever sent, so skip the
+    // prompt instead of asking the user for one that goes unused.
+    let password = match cli.command() {
+        Commands::Capacity(_) => String::new(),
+        _ => read_user_password()?,
+    };
     let file_bytes = get_input_file(cli.input_file()).await?;
 
     match query_attempt(&cli, &settings, &password, &file_bytes).await {
         Ok(()) => Ok(()),
         Err(err) if err.to_string() == "Connection failed" => {
             run_server(&cli, &settings).await?;
-            query_attempt_with_sleep(&cli, &settings, &password, &file_bytes, QUERY_ATTEMPTS).await
+            query_attempt_with_sleep(&cli, &settings, &password, &file_bytes).await
         }
         Err(err) => Err(err),
     }
 }
 
+/// Retries `query_attempt` against a just-(auto-)started server, backing off per
+/// `settings.retry` between attempts. Only a "Connection failed" error is retried;
+/// anything else (bad password, malformed carrier, ...) short-circuits immediately
+/// since waiting and retrying it would just fail the same way again.
 pub async fn query_attempt_with_sleep(
     cli: &Cli,
     settings: &Settings,
     password: &str,
     file_bytes: &[u8],
-    attempt: u8,
 ) -> Result<()> {
-    for _ in 0..attempt {
+    let retry = &settings.retry;
+
+    for attempt in 0..retry.max_attempts {
         match query_attempt(cli, settings, password, file_bytes).await {
             Ok(()) => return Ok(()),
             Err(err) if err.to_string() == "Connection failed" => {
-                sleep(Duration::from_secs(2)).await;
+                sleep(backoff_delay(retry, attempt as u32)).await;
             }
             Err(err) => return Err(err),
         }
@@ -63,6 +82,7 @@ pub async fn query_attempt(
             extract_command(extract, settings, password, file_bytes).await
         }
         Commands::Clear(clear) => clear_command(clear, settings, password, file_bytes).await,
+        Commands::Capacity(capacity) => capacity_command(capacity, settings, file_bytes).await,
     }
 }
 
@@ -82,17 +102,43 @@ pub async fn run_server(cli: &Cli, settings: &Settings) -> Result<()> {
         }
         StegoWaveServer::GRPC => {
             let addr: SocketAddr = settings.grpc_address()?.authority().parse()?;
+            let metrics_addr: SocketAddr = settings.grpc_metrics_address()?.authority().parse()?;
+            let auth = Arc::new(TokenAuthority::new(&settings.auth)?);
+            let tls = settings.tls.load()?;
+            let share_store: Arc<dyn ShareBackend> = Arc::new(ShareStore::new(&settings.share));
+            let pending_uploads: Arc<dyn PendingUploadBackend> = Arc::new(PendingUploadStore::new(
+                settings.pending_upload_expiry_secs,
+                settings.max_upload_bytes,
+            ));
+            let metrics_handle = install_recorder();
 
             drop(tokio::spawn(grpc_server::startup::run_server(
                 addr,
+                metrics_addr,
                 settings.stego_wave_lib.clone(),
+                auth,
+                metrics_handle,
+                tls,
+                share_store,
+                pending_uploads,
             )));
         }
-        StegoWaveServer::REST => {
+        // The `/ws/stego` gateway is mounted on the REST server itself, so starting
+        // it for `WebSocket` is the same as starting it for `REST`.
+        StegoWaveServer::REST | StegoWaveServer::WebSocket => {
             let listener: TcpListener = TcpListener::bind(settings.rest_address()?.authority())?;
+            let auth = Arc::new(TokenAuthority::new(&settings.auth)?);
+            let tls = settings.tls.load()?;
+            let share_store: Arc<dyn ShareBackend> = Arc::new(ShareStore::new(&settings.share));
 
-            let server =
-                rest_server::startup::run_server(listener, settings.stego_wave_lib.clone())?;
+            let server = rest_server::startup::run_server(
+                listener,
+                settings.stego_wave_lib.clone(),
+                auth,
+                tls,
+                share_store,
+                settings.rest.max_upload_bytes,
+            )?;
 
             drop(tokio::spawn(server));
         }
@@ -111,13 +157,17 @@ async fn hide_command(
         .await
         .map_err(stego_client_wrap_error)?;
 
+    let (format, lsb_deep) = hide.command.resolve_format_and_lsb_deep();
+
     let result: Vec<u8> = client
         .hide_message(
             file_bytes.to_vec(),
             hide.message.clone(),
             password.to_string(),
-            hide.command.format.clone().into(),
-            hide.command.lsb_deep,
+            format.into(),
+            lsb_deep,
+            hide.command.compress,
+            hide.command.encrypt,
         )
         .await?;
 
@@ -136,12 +186,14 @@ async fn extract_command(
         .await
         .map_err(stego_client_wrap_error)?;
 
+    let (format, lsb_deep) = extract.command.resolve_format_and_lsb_deep();
+
     let result: String = client
         .extract_message(
             file_bytes.to_vec(),
             password.to_string(),
-            extract.command.format.clone().into(),
-            extract.command.lsb_deep,
+            format.into(),
+            lsb_deep,
         )
         .await?;
 
@@ -159,12 +211,14 @@ async fn clear_command(
         .await
         .map_err(stego_client_wrap_error)?;
 
+    let (format, lsb_deep) = clear.command.resolve_format_and_lsb_deep();
+
     let result: Vec<u8> = client
         .clear_message(
             file_bytes.to_vec(),
             password.to_string(),
-            clear.command.format.clone().into(),
-            clear.command.lsb_deep,
+            format.into(),
+            lsb_deep,
         )
         .await?;
 
@@ -172,7 +226,26 @@ async fn clear_command(
     Ok(())
 }
 
-fn stego_client_wrap_error(err: StegoWaveClientError) -> Report {
+async fn capacity_command(
+    capacity: &CapacityCommand,
+    settings: &Settings,
+    file_bytes: &[u8],
+) -> Result<()> {
+    let mut client = get_client(&capacity.command.server, settings)
+        .await
+        .map_err(stego_client_wrap_error)?;
+
+    let (format, lsb_deep) = capacity.command.resolve_format_and_lsb_deep();
+
+    let (capacity_bytes, overhead_bytes) = client
+        .capacity(file_bytes.to_vec(), format.into(), lsb_deep)
+        .await?;
+
+    print_success!(capacity: capacity_bytes, overhead_bytes);
+    Ok(())
+}
+
+pub(crate) fn stego_client_wrap_error(err: StegoWaveClientError) -> Report {
     if let Some(help_message) = err.help_message() {
         Report::msg(err.to_string()).suggestion(help_message)
     } else {
@@ -180,12 +253,13 @@ fn stego_client_wrap_error(err: StegoWaveClientError) -> Report {
     }
 }
 
-async fn get_client(
+pub(crate) async fn get_client(
     server: &StegoWaveServer,
     settings: &Settings,
 ) -> Result<Box<dyn StegoWaveClient>, StegoWaveClientError> {
     let grpc_address = settings.grpc_address()?;
     let rest_address = settings.rest_address()?;
+    let ws_address = settings.ws_address()?;
 
     match server {
         StegoWaveServer::Auto => {
@@ -193,8 +267,10 @@ async fn get_client(
                 grpc_client::StegoWaveGrpcClient::new(grpc_address.to_string()).await
             {
                 Ok(Box::new(client))
+            } else if let Ok(client) = rest_client::StegoWaveRestClient::new(rest_address).await {
+                Ok(Box::new(client))
             } else {
-                let client = rest_client::StegoWaveRestClient::new(rest_address).await?;
+                let client = ws_client::StegoWaveWsClient::new(ws_address).await?;
                 Ok(Box::new(client))
             }
         }
@@ -206,10 +282,14 @@ async fn get_client(
             let client = rest_client::StegoWaveRestClient::new(rest_address).await?;
             Ok(Box::new(client))
         }
+        StegoWaveServer::WebSocket => {
+            let client = ws_client::StegoWaveWsClient::new(ws_address).await?;
+            Ok(Box::new(client))
+        }
     }
 }
 
-fn read_user_password() -> Result<String, io::Error> {
+pub(crate) fn read_user_password() -> Result<String, io::Error> {
     eprint!("[?] Enter password: ");
     stderr().flush()?;
 
@@ -1,15 +1,42 @@
+use crate::retry::RetryConfig;
 use grpc_server::configuration::GrpcConfig;
 use rest_server::configuration::RestConfig;
 use serde::Deserialize;
+use stego_wave::auth::AuthConfig;
 use stego_wave::configuration::StegoWaveLib;
 use stego_wave::error::StegoWaveClientError;
+use stego_wave::share::ShareConfig;
+use stego_wave::tls::TlsConfig;
 use url::Url;
 
+fn default_pending_upload_expiry_secs() -> u64 {
+    300
+}
+
+fn default_max_upload_bytes() -> u64 {
+    500 * 1024 * 1024
+}
+
 #[derive(Deserialize)]
 pub struct Settings {
     pub rest: RestConfig,
     pub grpc: GrpcConfig,
     pub stego_wave_lib: StegoWaveLib,
+    #[serde(default)]
+    pub retry: RetryConfig,
+    pub auth: AuthConfig,
+    #[serde(default)]
+    pub tls: TlsConfig,
+    #[serde(default)]
+    pub share: ShareConfig,
+    /// Mirrors `grpc_server::configuration::Settings::pending_upload_expiry_secs`,
+    /// used when `--start-server` brings up a gRPC server on this same config file.
+    #[serde(default = "default_pending_upload_expiry_secs")]
+    pub pending_upload_expiry_secs: u64,
+    /// Mirrors `grpc_server::configuration::Settings::max_upload_bytes`; the REST
+    /// equivalent already lives on `RestConfig::max_upload_bytes`.
+    #[serde(default = "default_max_upload_bytes")]
+    pub max_upload_bytes: u64,
 }
 
 impl Settings {
@@ -31,4 +58,17 @@ impl Settings {
         Url::parse(&format!("http://{}:{}", self.rest.host, self.rest.port))
             .map_err(|err| StegoWaveClientError::UlrInvalid(err.to_string()))
     }
+
+    pub fn grpc_metrics_address(&self) -> Result<Url, StegoWaveClientError> {
+        Url::parse(&format!("http://{}:{}", self.grpc.host, self.grpc.metrics_port))
+            .map_err(|err| StegoWaveClientError::UlrInvalid(err.to_string()))
+    }
+
+    /// The `/ws/stego` gateway is mounted on the REST server itself (see
+    /// `rest_server::startup::run_server`), so this just reuses the REST host/port
+    /// under the `ws` scheme instead of introducing a separate config section.
+    pub fn ws_address(&self) -> Result<Url, StegoWaveClientError> {
+        Url::parse(&format!("ws://{}:{}/ws/stego", self.rest.host, self.rest.port))
+            .map_err(|err| StegoWaveClientError::UlrInvalid(err.to_string()))
+    }
 }
@@ -17,4 +17,39 @@ macro_rules! print_success {
         println!("{}", $message.red().bold().underline());
         eprintln!();
     };
+
+    (batch: $succeeded:expr, $failed:expr, $total:expr) => {
+        print_success_helper("Batch run complete");
+        println!(
+            "{} {}",
+            "succeeded:".white().bold(),
+            $succeeded.to_string().green().bold()
+        );
+        println!(
+            "{} {}",
+            "failed:".white().bold(),
+            $failed.to_string().red().bold()
+        );
+        println!(
+            "{} {}",
+            "total:".white().bold(),
+            $total.to_string().white().bold()
+        );
+        eprintln!();
+    };
+
+    (capacity: $capacity:expr, $overhead:expr) => {
+        print_success_helper("Capacity computed");
+        println!(
+            "{} {}",
+            "capacity_bytes:".white().bold(),
+            $capacity.to_string().red().bold().underline()
+        );
+        println!(
+            "{} {}",
+            "overhead_bytes:".white().bold(),
+            $overhead.to_string().red().bold().underline()
+        );
+        eprintln!();
+    };
 }
@@ -1,9 +1,11 @@
 use colored::Colorize;
 
+mod batch;
 mod cli;
 mod client_request;
 mod configuration;
 mod formating;
+mod retry;
 mod startup;
 
 const CONFIG_FILE: &str = "sw_config.toml";
@@ -0,0 +1,111 @@
+use rand::Rng;
+use serde::Deserialize;
+use std::time::Duration;
+
+fn default_initial_delay_ms() -> u64 {
+    500
+}
+
+fn default_multiplier() -> f64 {
+    2.0
+}
+
+fn default_max_delay_ms() -> u64 {
+    30_000
+}
+
+fn default_max_attempts() -> u8 {
+    5
+}
+
+fn default_jitter() -> bool {
+    true
+}
+
+/// Exponential-backoff policy for retrying a "Connection failed" query against a
+/// slow-starting server, tunable per deployment via `Settings`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct RetryConfig {
+    #[serde(default = "default_initial_delay_ms")]
+    pub initial_delay_ms: u64,
+    #[serde(default = "default_multiplier")]
+    pub multiplier: f64,
+    #[serde(default = "default_max_delay_ms")]
+    pub max_delay_ms: u64,
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u8,
+    /// Adds a random fraction of the computed delay on top of it, so several CLI
+    /// invocations that raced to auto-start the same server don't all retry in lockstep.
+    #[serde(default = "default_jitter")]
+    pub jitter: bool,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            initial_delay_ms: default_initial_delay_ms(),
+            multiplier: default_multiplier(),
+            max_delay_ms: default_max_delay_ms(),
+            max_attempts: default_max_attempts(),
+            jitter: default_jitter(),
+        }
+    }
+}
+
+/// Computes `min(initial_delay * multiplier^attempt, max_delay)`, plus a uniformly
+/// random extra amount in `[0, delay)` when `config.jitter` is set.
+pub fn backoff_delay(config: &RetryConfig, attempt: u32) -> Duration {
+    let uncapped = config.initial_delay_ms as f64 * config.multiplier.powi(attempt as i32);
+    let delay_ms = uncapped.min(config.max_delay_ms as f64).max(0.0);
+
+    let delay_ms = if config.jitter && delay_ms > 0.0 {
+        delay_ms + rand::thread_rng().gen_range(0.0..delay_ms)
+    } else {
+        delay_ms
+    };
+
+    Duration::from_millis(delay_ms as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> RetryConfig {
+        RetryConfig {
+            initial_delay_ms: 100,
+            multiplier: 2.0,
+            max_delay_ms: 1_000,
+            max_attempts: 5,
+            jitter: false,
+        }
+    }
+
+    #[test]
+    fn delay_grows_exponentially_without_jitter() {
+        let config = config();
+
+        assert_eq!(backoff_delay(&config, 0), Duration::from_millis(100));
+        assert_eq!(backoff_delay(&config, 1), Duration::from_millis(200));
+        assert_eq!(backoff_delay(&config, 2), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn delay_is_capped_at_max_delay() {
+        let config = config();
+
+        assert_eq!(backoff_delay(&config, 10), Duration::from_millis(1_000));
+    }
+
+    #[test]
+    fn jitter_never_shrinks_the_base_delay() {
+        let mut config = config();
+        config.jitter = true;
+
+        for attempt in 0..5 {
+            let base = config().initial_delay_ms as f64 * config.multiplier.powi(attempt as i32);
+            let base = base.min(config.max_delay_ms as f64);
+            assert!(backoff_delay(&config, attempt).as_secs_f64() * 1000.0 >= base);
+        }
+    }
+}
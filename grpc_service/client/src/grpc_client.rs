@@ -1,12 +1,41 @@
 use crate::stego_wave_grpc::stego_wave_service_client::StegoWaveServiceClient;
-use crate::stego_wave_grpc::{ClearRequest, ExtractRequest, HideRequest};
+use crate::stego_wave_grpc::{CapacityRequest, ClearRequest, ExtractRequest, HideRequest};
+use crate::streaming::{chunk_requests, get_output_audio};
 use stego_wave::error::StegoWaveClientError;
 use stego_wave::object::StegoWaveClient;
+use tokio::io::AsyncRead;
+use tonic::codec::CompressionEncoding;
 use tonic::codegen::Bytes;
-use tonic::transport::Channel;
+use tonic::transport::{Channel, ClientTlsConfig};
 
 const MAX_MESSAGE_SIZE: usize = 100 * 1024 * 1024;
 
+/// Size, in bytes, of the chunks a file is split into before streaming it to the server.
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// How many times a `Vec<u8>`-based upload resumes from the server's reported
+/// offset before giving up and surfacing the incomplete-upload error as-is.
+const MAX_RESUME_ATTEMPTS: u32 = 3;
+
+/// A fresh random id naming one logical (possibly multi-call, resumed) upload.
+fn new_upload_id() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+/// Reads the resume offset `aggregate_file_from_stream` embeds in the message of
+/// the `Aborted` status it returns for a known-length upload that ended short of
+/// its declared `total_len`, so a dropped connection can be retried by resending
+/// only the missing tail instead of the whole file. Any other status, or an
+/// `Aborted` one that doesn't carry the expected phrase, isn't resumable.
+fn resume_offset_from_status(status: &tonic::Status) -> Option<usize> {
+    if status.code() != tonic::Code::Aborted {
+        return None;
+    }
+
+    let tail = status.message().rsplit_once("offset ")?.1;
+    tail.trim_end_matches('.').parse().ok()
+}
+
 #[derive(Clone)]
 pub struct StegoWaveGrpcClient {
     client: StegoWaveServiceClient<Channel>,
@@ -14,60 +43,116 @@ pub struct StegoWaveGrpcClient {
 
 impl StegoWaveGrpcClient {
     pub async fn new(url: impl Into<Bytes> + Send) -> Result<Self, StegoWaveClientError> {
-        let channel = Channel::from_shared(url)
-            .map_err(|err| StegoWaveClientError::UlrInvalid(err.to_string()))?
+        let url: Bytes = url.into();
+        let is_tls = url.starts_with(b"https://");
+
+        let mut endpoint = Channel::from_shared(url)
+            .map_err(|err| StegoWaveClientError::UlrInvalid(err.to_string()))?;
+
+        if is_tls {
+            // Trust the platform's native root store rather than requiring callers to
+            // supply a CA bundle, matching how `StegoWaveRestClient` trusts an
+            // `https://` server URL without any extra configuration of its own.
+            endpoint = endpoint
+                .tls_config(ClientTlsConfig::new().with_native_roots())
+                .map_err(|_err| StegoWaveClientError::ConnectionFailed)?;
+        }
+
+        let channel = endpoint
             .connect()
             .await
             .map_err(|_err| StegoWaveClientError::ConnectionFailed)?;
 
         let client = StegoWaveServiceClient::new(channel)
             .max_decoding_message_size(MAX_MESSAGE_SIZE)
-            .max_encoding_message_size(MAX_MESSAGE_SIZE);
+            .max_encoding_message_size(MAX_MESSAGE_SIZE)
+            .send_compressed(CompressionEncoding::Gzip)
+            .accept_compressed(CompressionEncoding::Gzip);
 
         Ok(Self { client })
     }
-}
 
-#[async_trait::async_trait]
-impl StegoWaveClient for StegoWaveGrpcClient {
-    async fn hide_message(
+    /// Streams `reader` to the server chunk by chunk instead of buffering the whole
+    /// file in memory first, so a multi-hundred-MB cover file can be hidden into
+    /// with bounded memory. See [`crate::streaming::chunk_requests`]. The
+    /// [`StegoWaveClient::hide_message`] impl below is the `Vec<u8>` convenience
+    /// form of this for callers that already have the whole file resident.
+    pub async fn hide_message_from_reader(
         &mut self,
-        file: Vec<u8>,
+        reader: impl AsyncRead + Send + Unpin + 'static,
         message: String,
         password: String,
         format: String,
         lsb_deep: u8,
+        compress: bool,
+        encrypt: bool,
     ) -> Result<Vec<u8>, StegoWaveClientError> {
-        let response = self
+        let mut metadata = Some((message, password, format, lsb_deep, compress, encrypt));
+        let request_stream = chunk_requests(
+            reader,
+            CHUNK_SIZE,
+            new_upload_id(),
+            move |file, offset, upload_id| {
+                let (message, password, format, lsb_deep, compress, encrypt) =
+                    metadata.take().expect("first chunk is only produced once");
+                HideRequest {
+                    file,
+                    message,
+                    password,
+                    format,
+                    lsb_deep: lsb_deep as _,
+                    compress,
+                    encrypt,
+                    offset,
+                    total_len: 0,
+                    upload_id: upload_id.to_string(),
+                }
+            },
+            HideRequest::create_by_chunk,
+        );
+
+        let response_stream = self
             .client
-            .hide_message(HideRequest {
-                file,
-                message,
-                password,
-                format,
-                lsb_deep: lsb_deep as _,
-            })
+            .hide_message(request_stream)
             .await
-            .map_err(|err| StegoWaveClientError::Response(err.message().to_string()))?;
+            .map_err(|err| StegoWaveClientError::Response(err.message().to_string()))?
+            .into_inner();
 
-        Ok(response.into_inner().file)
+        get_output_audio(response_stream).await
     }
 
-    async fn extract_message(
+    /// Streams `reader` to the server chunk by chunk; see [`Self::hide_message_from_reader`].
+    pub async fn extract_message_from_reader(
         &mut self,
-        file: Vec<u8>,
+        reader: impl AsyncRead + Send + Unpin + 'static,
         password: String,
         format: String,
         lsb_deep: u8,
     ) -> Result<String, StegoWaveClientError> {
+        let mut metadata = Some((password, format, lsb_deep));
+        let request_stream = chunk_requests(
+            reader,
+            CHUNK_SIZE,
+            new_upload_id(),
+            move |file, offset, upload_id| {
+                let (password, format, lsb_deep) =
+                    metadata.take().expect("first chunk is only produced once");
+                ExtractRequest {
+                    file,
+                    password,
+                    format,
+                    lsb_deep: lsb_deep as _,
+                    offset,
+                    total_len: 0,
+                    upload_id: upload_id.to_string(),
+                }
+            },
+            ExtractRequest::create_by_chunk,
+        );
+
         let response = self
             .client
-            .extract_message(ExtractRequest {
-                file,
-                password,
-                format,
-                lsb_deep: lsb_deep as _,
-            })
+            .extract_message(request_stream)
             .await
             .map_err(|err| StegoWaveClientError::Response(err.message().to_string()))?
             .into_inner();
@@ -75,24 +160,336 @@ impl StegoWaveClient for StegoWaveGrpcClient {
         Ok(response.message)
     }
 
-    async fn clear_message(
+    /// Streams `reader` to the server chunk by chunk; see [`Self::hide_message_from_reader`].
+    pub async fn clear_message_from_reader(
         &mut self,
-        file: Vec<u8>,
+        reader: impl AsyncRead + Send + Unpin + 'static,
         password: String,
         format: String,
         lsb_deep: u8,
     ) -> Result<Vec<u8>, StegoWaveClientError> {
+        let mut metadata = Some((password, format, lsb_deep));
+        let request_stream = chunk_requests(
+            reader,
+            CHUNK_SIZE,
+            new_upload_id(),
+            move |file, offset, upload_id| {
+                let (password, format, lsb_deep) =
+                    metadata.take().expect("first chunk is only produced once");
+                ClearRequest {
+                    file,
+                    password,
+                    format,
+                    lsb_deep: lsb_deep as _,
+                    offset,
+                    total_len: 0,
+                    upload_id: upload_id.to_string(),
+                }
+            },
+            ClearRequest::create_by_chunk,
+        );
+
+        let response_stream = self
+            .client
+            .clear_message(request_stream)
+            .await
+            .map_err(|err| StegoWaveClientError::Response(err.message().to_string()))?
+            .into_inner();
+
+        get_output_audio(response_stream).await
+    }
+}
+
+#[async_trait::async_trait]
+impl StegoWaveClient for StegoWaveGrpcClient {
+    async fn capacity(
+        &mut self,
+        file: Vec<u8>,
+        format: String,
+        lsb_deep: u8,
+    ) -> Result<(usize, usize), StegoWaveClientError> {
+        let request = CapacityRequest {
+            file,
+            format,
+            lsb_deep: lsb_deep as _,
+        };
+
         let response = self
             .client
-            .clear_message(ClearRequest {
-                file,
-                password,
-                format,
-                lsb_deep: lsb_deep as _,
-            })
+            .capacity(request)
             .await
-            .map_err(|err| StegoWaveClientError::Response(err.message().to_string()))?;
+            .map_err(|err| StegoWaveClientError::Response(err.message().to_string()))?
+            .into_inner();
+
+        Ok((
+            response.capacity_bytes as usize,
+            response.overhead_bytes as usize,
+        ))
+    }
+
+    /// Streams `file` to the server in one shot, unlike the resumable
+    /// [`Self::hide_message`]/[`Self::extract_message`]/[`Self::clear_message`]
+    /// below, since a dry-run capacity query isn't worth resuming.
+    async fn hide_message(
+        &mut self,
+        file: Vec<u8>,
+        message: String,
+        password: String,
+        format: String,
+        lsb_deep: u8,
+        compress: bool,
+        encrypt: bool,
+    ) -> Result<Vec<u8>, StegoWaveClientError> {
+        let total_len = file.len() as u64;
+        let upload_id = new_upload_id();
+        let mut resume_from = 0usize;
+
+        for _ in 0..=MAX_RESUME_ATTEMPTS {
+            let requests = hide_requests(
+                &file,
+                resume_from,
+                total_len,
+                &upload_id,
+                &message,
+                &password,
+                &format,
+                lsb_deep,
+                compress,
+                encrypt,
+            );
+
+            match self
+                .client
+                .hide_message(tokio_stream::iter(requests))
+                .await
+            {
+                Ok(response) => return get_output_audio(response.into_inner()).await,
+                Err(status) => match resume_offset_from_status(&status) {
+                    Some(offset) => resume_from = offset,
+                    None => {
+                        return Err(StegoWaveClientError::Response(status.message().to_string()));
+                    }
+                },
+            }
+        }
 
-        Ok(response.into_inner().file)
+        Err(StegoWaveClientError::Response(format!(
+            "Upload did not complete after {MAX_RESUME_ATTEMPTS} resume attempt(s)"
+        )))
     }
+
+    async fn extract_message(
+        &mut self,
+        file: Vec<u8>,
+        password: String,
+        format: String,
+        lsb_deep: u8,
+    ) -> Result<String, StegoWaveClientError> {
+        let total_len = file.len() as u64;
+        let upload_id = new_upload_id();
+        let mut resume_from = 0usize;
+
+        for _ in 0..=MAX_RESUME_ATTEMPTS {
+            let requests = extract_requests(
+                &file,
+                resume_from,
+                total_len,
+                &upload_id,
+                &password,
+                &format,
+                lsb_deep,
+            );
+
+            match self
+                .client
+                .extract_message(tokio_stream::iter(requests))
+                .await
+            {
+                Ok(response) => return Ok(response.into_inner().message),
+                Err(status) => match resume_offset_from_status(&status) {
+                    Some(offset) => resume_from = offset,
+                    None => {
+                        return Err(StegoWaveClientError::Response(status.message().to_string()));
+                    }
+                },
+            }
+        }
+
+        Err(StegoWaveClientError::Response(format!(
+            "Upload did not complete after {MAX_RESUME_ATTEMPTS} resume attempt(s)"
+        )))
+    }
+
+    async fn clear_message(
+        &mut self,
+        file: Vec<u8>,
+        password: String,
+        format: String,
+        lsb_deep: u8,
+    ) -> Result<Vec<u8>, StegoWaveClientError> {
+        let total_len = file.len() as u64;
+        let upload_id = new_upload_id();
+        let mut resume_from = 0usize;
+
+        for _ in 0..=MAX_RESUME_ATTEMPTS {
+            let requests = clear_requests(
+                &file,
+                resume_from,
+                total_len,
+                &upload_id,
+                &password,
+                &format,
+                lsb_deep,
+            );
+
+            match self
+                .client
+                .clear_message(tokio_stream::iter(requests))
+                .await
+            {
+                Ok(response) => return get_output_audio(response.into_inner()).await,
+                Err(status) => match resume_offset_from_status(&status) {
+                    Some(offset) => resume_from = offset,
+                    None => {
+                        return Err(StegoWaveClientError::Response(status.message().to_string()));
+                    }
+                },
+            }
+        }
+
+        Err(StegoWaveClientError::Response(format!(
+            "Upload did not complete after {MAX_RESUME_ATTEMPTS} resume attempt(s)"
+        )))
+    }
+}
+
+/// Splits `file[resume_from..]` into `CHUNK_SIZE`-sized slices, yielding a single
+/// empty slice instead of nothing when there's no remaining data and `resume_from`
+/// is `0`, so an empty file still produces one request to carry its metadata
+/// (mirroring [`chunk_requests`](crate::streaming::chunk_requests)'s same rule).
+fn byte_chunks(file: &[u8], resume_from: usize) -> impl Iterator<Item = &[u8]> {
+    let remaining = &file[resume_from..];
+    let empty_file = resume_from == 0 && remaining.is_empty();
+
+    remaining
+        .chunks(CHUNK_SIZE)
+        .chain(empty_file.then_some(remaining))
+}
+
+/// Splits `file[resume_from..]` into `CHUNK_SIZE` requests, each carrying its real
+/// offset into the whole file and `total_len` so the server can both validate
+/// contiguity and tell the client, via an `Aborted` status, where to resume if this
+/// attempt doesn't make it all the way. Only the very first request of the very
+/// first attempt (`resume_from == 0`) carries the hide metadata; a resumed
+/// attempt's first request starts at a nonzero offset and leaves it blank, since
+/// the server already has it from the earlier attempt.
+#[allow(clippy::too_many_arguments)]
+fn hide_requests(
+    file: &[u8],
+    resume_from: usize,
+    total_len: u64,
+    upload_id: &str,
+    message: &str,
+    password: &str,
+    format: &str,
+    lsb_deep: u8,
+    compress: bool,
+    encrypt: bool,
+) -> Vec<HideRequest> {
+    byte_chunks(file, resume_from)
+        .scan(resume_from as u64, |offset, chunk| {
+            let this_offset = *offset;
+            *offset += chunk.len() as u64;
+
+            Some(if this_offset == 0 {
+                HideRequest {
+                    file: chunk.to_owned(),
+                    message: message.to_string(),
+                    password: password.to_string(),
+                    format: format.to_string(),
+                    lsb_deep: lsb_deep as _,
+                    compress,
+                    encrypt,
+                    offset: this_offset,
+                    total_len,
+                    upload_id: upload_id.to_string(),
+                }
+            } else {
+                HideRequest {
+                    total_len,
+                    ..HideRequest::create_by_chunk(chunk, this_offset, upload_id)
+                }
+            })
+        })
+        .collect()
+}
+
+/// See [`hide_requests`].
+fn extract_requests(
+    file: &[u8],
+    resume_from: usize,
+    total_len: u64,
+    upload_id: &str,
+    password: &str,
+    format: &str,
+    lsb_deep: u8,
+) -> Vec<ExtractRequest> {
+    byte_chunks(file, resume_from)
+        .scan(resume_from as u64, |offset, chunk| {
+            let this_offset = *offset;
+            *offset += chunk.len() as u64;
+
+            Some(if this_offset == 0 {
+                ExtractRequest {
+                    file: chunk.to_owned(),
+                    password: password.to_string(),
+                    format: format.to_string(),
+                    lsb_deep: lsb_deep as _,
+                    offset: this_offset,
+                    total_len,
+                    upload_id: upload_id.to_string(),
+                }
+            } else {
+                ExtractRequest {
+                    total_len,
+                    ..ExtractRequest::create_by_chunk(chunk, this_offset, upload_id)
+                }
+            })
+        })
+        .collect()
+}
+
+/// See [`hide_requests`].
+fn clear_requests(
+    file: &[u8],
+    resume_from: usize,
+    total_len: u64,
+    upload_id: &str,
+    password: &str,
+    format: &str,
+    lsb_deep: u8,
+) -> Vec<ClearRequest> {
+    byte_chunks(file, resume_from)
+        .scan(resume_from as u64, |offset, chunk| {
+            let this_offset = *offset;
+            *offset += chunk.len() as u64;
+
+            Some(if this_offset == 0 {
+                ClearRequest {
+                    file: chunk.to_owned(),
+                    password: password.to_string(),
+                    format: format.to_string(),
+                    lsb_deep: lsb_deep as _,
+                    offset: this_offset,
+                    total_len,
+                    upload_id: upload_id.to_string(),
+                }
+            } else {
+                ClearRequest {
+                    total_len,
+                    ..ClearRequest::create_by_chunk(chunk, this_offset, upload_id)
+                }
+            })
+        })
+        .collect()
 }
@@ -1,42 +1,113 @@
 use crate::stego_wave_grpc::{AudioResponse, ClearRequest, ExtractRequest, HideRequest};
-use futures::StreamExt;
+use futures::{Stream, StreamExt, stream::unfold};
 use stego_wave::error::StegoWaveClientError;
+use tokio::io::AsyncRead;
+use tokio_util::io::ReaderStream;
 use tonic::Streaming;
 
 impl HideRequest {
-    pub fn create_by_chunk(chunk: &[u8]) -> Self {
+    /// Builds a non-first chunk at `offset` of the upload named `upload_id`,
+    /// carrying no `total_len` (see [`chunk_requests`]'s doc comment for why
+    /// that's the right default for every caller of this constructor).
+    pub fn create_by_chunk(chunk: &[u8], offset: u64, upload_id: &str) -> Self {
         Self {
             file: chunk.to_owned(),
             message: "".to_string(),
             password: "".to_string(),
             format: "".to_string(),
             lsb_deep: 0,
+            compress: false,
+            encrypt: false,
+            offset,
+            total_len: 0,
+            upload_id: upload_id.to_string(),
         }
     }
 }
 
 impl ExtractRequest {
-    pub fn create_by_chunk(chunk: &[u8]) -> ExtractRequest {
+    pub fn create_by_chunk(chunk: &[u8], offset: u64, upload_id: &str) -> ExtractRequest {
         Self {
             file: chunk.to_owned(),
             password: "".to_string(),
             format: "".to_string(),
             lsb_deep: 0,
+            offset,
+            total_len: 0,
+            upload_id: upload_id.to_string(),
         }
     }
 }
 
 impl ClearRequest {
-    pub fn create_by_chunk(chunk: &[u8]) -> ClearRequest {
+    pub fn create_by_chunk(chunk: &[u8], offset: u64, upload_id: &str) -> ClearRequest {
         Self {
             file: chunk.to_owned(),
             password: "".to_string(),
             format: "".to_string(),
             lsb_deep: 0,
+            offset,
+            total_len: 0,
+            upload_id: upload_id.to_string(),
         }
     }
 }
 
+/// Lazily turns `reader` into a stream of `Req`, pulling at most `chunk_size` bytes
+/// at a time instead of buffering the whole source up front, so a multi-hundred-MB
+/// file can be streamed with bounded memory, backpressured by however fast the gRPC
+/// send side drains the stream. The first chunk is built by `first` (carrying the
+/// request's metadata fields, the way `HideRequest::create_by_chunk` leaves them
+/// blank), every later chunk by `rest` (pure file bytes); both are also handed the
+/// byte offset of the chunk within `reader` and `upload_id`, so every request this
+/// produces names the same upload. Always yields at least one request, even for an
+/// empty `reader`, so the metadata is never dropped on the floor.
+///
+/// `reader`'s length isn't known upfront, so every chunk carries `total_len: 0`
+/// (see [`crate::stego_wave_grpc::HideRequest::create_by_chunk`]): the server
+/// accepts whatever contiguous prefix it receives rather than requiring an exact
+/// length, meaning a reader-sourced upload isn't resumable the way the `Vec<u8>`-based
+/// [`crate::grpc_client::StegoWaveGrpcClient::hide_message`] is.
+///
+/// A read error ends the stream early (after the first request, if one was already
+/// sent) rather than surfacing a `Result` item: the gRPC client streaming API this
+/// feeds only accepts a plain `Stream<Item = Req>`, so a truncated upload is left to
+/// fail the same way a corrupted one would, on the server's decrypt/verify step.
+pub fn chunk_requests<Req>(
+    reader: impl AsyncRead + Send + Unpin + 'static,
+    chunk_size: usize,
+    upload_id: String,
+    first: impl FnMut(Vec<u8>, u64, &str) -> Req + Send + 'static,
+    rest: impl FnMut(&[u8], u64, &str) -> Req + Send + 'static,
+) -> impl Stream<Item = Req> + Send
+where
+    Req: Send + 'static,
+{
+    let byte_stream = ReaderStream::with_capacity(reader, chunk_size);
+
+    unfold(
+        (byte_stream, 0u64, upload_id, first, rest),
+        |(mut byte_stream, offset, upload_id, mut first, mut rest)| async move {
+            match byte_stream.next().await {
+                Some(Ok(chunk)) => {
+                    let request = if offset == 0 {
+                        first(chunk.to_vec(), offset, &upload_id)
+                    } else {
+                        rest(&chunk, offset, &upload_id)
+                    };
+                    let offset = offset + chunk.len() as u64;
+                    Some((request, (byte_stream, offset, upload_id, first, rest)))
+                }
+                _ if offset == 0 => {
+                    let request = first(Vec::new(), 0, &upload_id);
+                    Some((request, (byte_stream, 1, upload_id, first, rest)))
+                }
+                _ => None,
+            }
+        },
+    )
+}
+
 #[inline]
 pub async fn get_output_audio(
     mut response_stream: Streaming<AudioResponse>,
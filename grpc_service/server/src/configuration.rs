@@ -1,16 +1,43 @@
 use serde::Deserialize;
+use stego_wave::auth::AuthConfig;
 use stego_wave::configuration::StegoWaveLib;
+use stego_wave::share::ShareConfig;
+use stego_wave::tls::TlsConfig;
 
 #[derive(Deserialize)]
 pub struct GrpcConfig {
     pub host: String,
     pub port: u32,
+    pub metrics_port: u32,
+}
+
+fn default_pending_upload_expiry_secs() -> u64 {
+    300
+}
+
+fn default_max_upload_bytes() -> u64 {
+    500 * 1024 * 1024
 }
 
 #[derive(Deserialize)]
 pub struct Settings {
     pub grpc: GrpcConfig,
     pub stego_wave_lib: StegoWaveLib,
+    pub auth: AuthConfig,
+    #[serde(default)]
+    pub tls: TlsConfig,
+    #[serde(default)]
+    pub share: ShareConfig,
+    /// How long a resumable upload session (see `services::pending_upload`) is
+    /// kept alive waiting for the rest of its bytes before it's swept away.
+    #[serde(default = "default_pending_upload_expiry_secs")]
+    pub pending_upload_expiry_secs: u64,
+    /// Hard cap on a chunk's `offset`, `total_len`, or `offset + chunk.len()`;
+    /// chunks over this are rejected with `Status::invalid_argument` before they
+    /// can grow a pending upload's reassembly buffer. Mirrors
+    /// `rest_service::configuration::RestConfig::max_upload_bytes`.
+    #[serde(default = "default_max_upload_bytes")]
+    pub max_upload_bytes: u64,
 }
 
 impl Settings {
@@ -26,4 +53,8 @@ impl Settings {
     pub fn address(&self) -> String {
         format!("{}:{}", self.grpc.host, self.grpc.port)
     }
+
+    pub fn metrics_address(&self) -> String {
+        format!("{}:{}", self.grpc.host, self.grpc.metrics_port)
+    }
 }
@@ -1,5 +1,10 @@
 use grpc_server::configuration;
+use grpc_server::services::{PendingUploadBackend, PendingUploadStore};
 use grpc_server::startup::run_server;
+use std::sync::Arc;
+use stego_wave::auth::TokenAuthority;
+use stego_wave::metrics::install_recorder;
+use stego_wave::share::{ShareBackend, ShareStore};
 
 const CONFIG_FILE: &str = "sw_config.toml";
 
@@ -7,7 +12,26 @@ const CONFIG_FILE: &str = "sw_config.toml";
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let settings = configuration::Settings::new(CONFIG_FILE)?;
     let addr = settings.address().parse()?;
+    let metrics_addr = settings.metrics_address().parse()?;
+    let auth = Arc::new(TokenAuthority::new(&settings.auth)?);
+    let tls = settings.tls.load()?;
+    let share_store: Arc<dyn ShareBackend> = Arc::new(ShareStore::new(&settings.share));
+    let pending_uploads: Arc<dyn PendingUploadBackend> = Arc::new(PendingUploadStore::new(
+        settings.pending_upload_expiry_secs,
+        settings.max_upload_bytes,
+    ));
+    let metrics_handle = install_recorder();
 
-    run_server(addr, settings.stego_wave_lib).await?;
+    run_server(
+        addr,
+        metrics_addr,
+        settings.stego_wave_lib,
+        auth,
+        metrics_handle,
+        tls,
+        share_store,
+        pending_uploads,
+    )
+    .await?;
     Ok(())
 }
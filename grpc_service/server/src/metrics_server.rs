@@ -0,0 +1,26 @@
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
+use metrics_exporter_prometheus::PrometheusHandle;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+/// Serves the Prometheus text exposition format at `/metrics` on `addr`.
+///
+/// gRPC has no natural place to hang a scrape endpoint off of the main service, so
+/// this runs as a tiny standalone HTTP server alongside it, mirroring the `/metrics`
+/// route the REST server exposes through actix.
+pub async fn run_metrics_server(addr: SocketAddr, handle: PrometheusHandle) {
+    let make_svc = make_service_fn(move |_conn| {
+        let handle = handle.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |_req: Request<Body>| {
+                let handle = handle.clone();
+                async move { Ok::<_, Infallible>(Response::new(Body::from(handle.render()))) }
+            }))
+        }
+    });
+
+    if let Err(err) = Server::bind(&addr).serve(make_svc).await {
+        eprintln!("metrics server error: {err}");
+    }
+}
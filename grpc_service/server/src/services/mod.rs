@@ -0,0 +1,6 @@
+pub mod pending_upload;
+mod stego_wave;
+mod streaming;
+
+pub use pending_upload::{PendingUploadBackend, PendingUploadStore};
+pub use stego_wave::StegoWaveServiceImpl;
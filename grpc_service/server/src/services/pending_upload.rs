@@ -0,0 +1,180 @@
+use crate::services::streaming::AudioMetadata;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use stego_wave::interval_set::IntervalSet;
+
+struct PendingUpload {
+    buffer: Vec<u8>,
+    received: IntervalSet,
+    total_len: u64,
+    metadata: Option<AudioMetadata>,
+    expires_at: SystemTime,
+}
+
+/// Why a chunk couldn't be merged into its upload session.
+pub enum MergeError {
+    /// The chunk's `[offset, offset + len)` shares bytes with a range already
+    /// received in this session.
+    Overlap,
+    /// The session reached its declared `total_len` without ever receiving a
+    /// first frame (offset `0`) carrying hide/extract/clear metadata.
+    MissingMetadata,
+    /// `offset`, `total_len`, or `offset + chunk.len()` exceeds the backend's
+    /// configured `max_upload_bytes`, so the chunk was rejected before it could
+    /// grow the session's reassembly buffer.
+    TooLarge,
+}
+
+/// Outcome of merging one chunk into a (possibly pre-existing) upload session.
+pub enum MergeOutcome {
+    /// Every byte up to the declared `total_len` has now arrived; the session is
+    /// consumed and the caller owns the reassembled file.
+    Complete {
+        file: Vec<u8>,
+        metadata: AudioMetadata,
+    },
+    /// Fewer than `total_len` contiguous bytes have arrived so far; the session
+    /// stays registered so a later call can resume it.
+    Incomplete { received_len: u64 },
+}
+
+/// Backend tracking in-flight, possibly multi-call uploads so a client whose
+/// connection drops partway through streaming a large file can resume by
+/// reopening the request stream with the same `upload_id` and only the bytes
+/// from `received_len` onward, instead of resending the whole file.
+///
+/// Mirrors [`stego_wave::share::ShareBackend`]'s shape: an in-memory store
+/// guarded by a mutex, swept on a TTL rather than torn down explicitly, so an
+/// abandoned upload doesn't accumulate forever.
+pub trait PendingUploadBackend: Send + Sync {
+    /// Merges one chunk (`offset..offset + chunk.len()`) into the session named
+    /// by `upload_id`, creating it on first use. `total_len` of `0` means the
+    /// source's length isn't known upfront (e.g. a piped stdin upload): such a
+    /// session never completes via this method and must be settled with
+    /// [`Self::finalize`] once the client closes its sending half.
+    fn merge(
+        &self,
+        upload_id: &str,
+        offset: u64,
+        total_len: u64,
+        chunk: &[u8],
+        metadata: Option<AudioMetadata>,
+    ) -> Result<MergeOutcome, MergeError>;
+
+    /// Settles a session whose request stream ended without `merge` ever
+    /// reporting it `Complete` — the only way a `total_len`-unknown upload (or a
+    /// genuinely incomplete one left at end-of-stream) is ever resolved. Returns
+    /// `Some` and removes the session only if what arrived is gapless from byte
+    /// `0`; a session with an internal gap, or one still short of a known
+    /// `total_len`, is left in place so a later call can still resume it.
+    fn finalize(&self, upload_id: &str) -> Option<(Vec<u8>, AudioMetadata)>;
+
+    /// Drops every session whose expiry has passed without completing.
+    fn sweep_expired(&self);
+}
+
+pub struct PendingUploadStore {
+    sessions: Mutex<HashMap<String, PendingUpload>>,
+    expiry_duration: Duration,
+    max_upload_bytes: u64,
+}
+
+impl PendingUploadStore {
+    pub fn new(expiry_duration_secs: u64, max_upload_bytes: u64) -> Self {
+        Self {
+            sessions: Mutex::new(HashMap::new()),
+            expiry_duration: Duration::from_secs(expiry_duration_secs),
+            max_upload_bytes,
+        }
+    }
+}
+
+impl PendingUploadBackend for PendingUploadStore {
+    fn merge(
+        &self,
+        upload_id: &str,
+        offset: u64,
+        total_len: u64,
+        chunk: &[u8],
+        metadata: Option<AudioMetadata>,
+    ) -> Result<MergeOutcome, MergeError> {
+        let end = offset
+            .checked_add(chunk.len() as u64)
+            .ok_or(MergeError::TooLarge)?;
+        if end > self.max_upload_bytes || total_len > self.max_upload_bytes {
+            return Err(MergeError::TooLarge);
+        }
+
+        let mut sessions = self.sessions.lock().unwrap();
+        let session = sessions
+            .entry(upload_id.to_string())
+            .or_insert_with(|| PendingUpload {
+                buffer: Vec::new(),
+                received: IntervalSet::new(),
+                total_len,
+                metadata: None,
+                expires_at: SystemTime::now() + self.expiry_duration,
+            });
+
+        let offset = offset as usize;
+        let end = end as usize;
+
+        if session.received.overlaps(offset, end) {
+            return Err(MergeError::Overlap);
+        }
+
+        session.received.insert(offset, end);
+        if session.buffer.len() < end {
+            session.buffer.resize(end, 0);
+        }
+        session.buffer[offset..end].copy_from_slice(chunk);
+        session.expires_at = SystemTime::now() + self.expiry_duration;
+        if total_len > 0 {
+            session.total_len = total_len;
+        }
+        if metadata.is_some() {
+            session.metadata = metadata;
+        }
+
+        let received_len = session.received.contiguous_prefix_len();
+        if session.total_len > 0 && received_len as u64 >= session.total_len {
+            let mut session = sessions.remove(upload_id).expect("just looked up above");
+            return match session.metadata.take() {
+                Some(metadata) => Ok(MergeOutcome::Complete {
+                    file: std::mem::take(&mut session.buffer),
+                    metadata,
+                }),
+                None => Err(MergeError::MissingMetadata),
+            };
+        }
+
+        Ok(MergeOutcome::Incomplete {
+            received_len: received_len as u64,
+        })
+    }
+
+    fn finalize(&self, upload_id: &str) -> Option<(Vec<u8>, AudioMetadata)> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let session = sessions.get(upload_id)?;
+        let received_len = session.received.contiguous_prefix_len();
+
+        let is_gapless = received_len == session.buffer.len();
+        let is_done = session.total_len == 0 && is_gapless;
+        if !is_done {
+            return None;
+        }
+
+        let mut session = sessions.remove(upload_id)?;
+        let metadata = session.metadata.take()?;
+        Some((std::mem::take(&mut session.buffer), metadata))
+    }
+
+    fn sweep_expired(&self) {
+        let now = SystemTime::now();
+        self.sessions
+            .lock()
+            .unwrap()
+            .retain(|_, session| session.expires_at > now);
+    }
+}
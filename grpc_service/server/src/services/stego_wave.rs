@@ -1,114 +1,257 @@
+use std::pin::Pin;
 use std::sync::Arc;
-use stego_wave::AudioSteganography;
+use std::time::Instant;
+use stego_wave::command::{CommandError, StegoCommand, StegoOutcome, execute};
 use stego_wave::configuration::StegoWaveLib;
 use stego_wave::formats::get_stego_by_str;
-use tonic::{Request, Response, Status};
+use stego_wave::metrics::{FailureKind, failure_kind_for, record_failure, record_success};
+use stego_wave::share::ShareBackend;
+use tonic::codegen::tokio_stream::Stream;
+use tonic::{Request, Response, Status, Streaming};
 
+use crate::services::pending_upload::PendingUploadBackend;
+use crate::services::streaming::{AudioMetadata, aggregate_file_from_stream, stream_file_as_chunks};
 use crate::stego_wave_grpc::{
-    AudioResponse, ClearRequest, ExtractRequest, HideRequest, MessageResponse,
-    stego_wave_service_server::StegoWaveService,
+    AudioResponse, CapacityRequest, CapacityResponse, ClearRequest, ExtractRequest,
+    FetchResultRequest, HideRequest, MessageResponse, stego_wave_service_server::StegoWaveService,
 };
 
-#[derive(Default)]
+/// Size, in bytes, of the chunks the processed audio is streamed back in.
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+fn command_failure_kind(err: &CommandError) -> FailureKind {
+    match err {
+        CommandError::UnknownFormat(_) => FailureKind::InvalidArgument,
+        CommandError::Stego(err) => failure_kind_for(err),
+    }
+}
+
+fn command_to_status(err: CommandError) -> Status {
+    match command_failure_kind(&err) {
+        FailureKind::InvalidArgument => Status::invalid_argument(err.to_string()),
+        FailureKind::Internal => Status::internal(err.to_string()),
+    }
+}
+
 pub struct StegoWaveServiceImpl {
     settings: Arc<StegoWaveLib>,
+    share_store: Arc<dyn ShareBackend>,
+    pending_uploads: Arc<dyn PendingUploadBackend>,
 }
 
 impl StegoWaveServiceImpl {
-    pub fn new(settings: StegoWaveLib) -> Self {
+    pub fn new(
+        settings: StegoWaveLib,
+        share_store: Arc<dyn ShareBackend>,
+        pending_uploads: Arc<dyn PendingUploadBackend>,
+    ) -> Self {
         Self {
             settings: Arc::new(settings),
+            share_store,
+            pending_uploads,
         }
     }
 }
 
 macro_rules! get_stego {
-    ($format:expr, $lsb_deep:expr, $settings:expr) => {
-        match get_stego_by_str(&$format, $lsb_deep as _, (*$settings).clone()) {
+    ($operation:expr, $format:expr, $lsb_deep:expr, $compress:expr, $settings:expr) => {
+        match get_stego_by_str(&$format, $lsb_deep as _, $compress, (*$settings).clone()) {
             Ok(stego) => stego,
-            Err(err) => return Err(Status::invalid_argument(err.to_string())),
+            Err(err) => {
+                record_failure($operation, &$format, $lsb_deep as _, FailureKind::InvalidArgument);
+                return Err(Status::invalid_argument(err.to_string()));
+            }
         }
     };
 }
 
+type AudioResponseStream = Pin<Box<dyn Stream<Item = Result<AudioResponse, Status>> + Send>>;
+
 #[tonic::async_trait]
 impl StegoWaveService for StegoWaveServiceImpl {
+    type HideMessageStream = AudioResponseStream;
+    type ClearMessageStream = AudioResponseStream;
+    type FetchResultStream = AudioResponseStream;
+
     async fn hide_message(
         &self,
-        request: Request<HideRequest>,
-    ) -> Result<Response<AudioResponse>, Status> {
-        let HideRequest {
-            file,
+        request: Request<Streaming<HideRequest>>,
+    ) -> Result<Response<Self::HideMessageStream>, Status> {
+        let start = Instant::now();
+        let (file, metadata) =
+            aggregate_file_from_stream(request.into_inner(), self.pending_uploads.as_ref()).await?;
+        let AudioMetadata::Hide {
             message,
             password,
             format,
             lsb_deep,
-        } = request.into_inner();
-        let stego = get_stego!(format, lsb_deep, self.settings);
-
-        let (mut samples, spec) = stego
-            .read_samples_from_byte(file)
-            .map_err(|err| Status::internal(err.to_string()))?;
+            compress,
+            encrypt,
+        } = metadata
+        else {
+            return Err(Status::invalid_argument(
+                "Expected hide metadata in the first frame",
+            ));
+        };
+        let input_len = file.len();
+        let lsb_deep = lsb_deep as u8;
 
-        stego
-            .hide_message_binary(&mut samples, &message, &password)
-            .map_err(|err| Status::internal(err.to_string()))?;
-
-        let output_byte = stego
-            .write_samples_to_byte(spec, &samples)
-            .map_err(|err| Status::internal(err.to_string()))?;
+        let command = StegoCommand::Hide {
+            file,
+            message,
+            password,
+            format: format.clone(),
+            lsb_deep,
+            compress,
+            encrypt,
+        };
 
-        Ok(Response::new(AudioResponse { file: output_byte }))
+        match execute(command, (*self.settings).clone()) {
+            Ok(StegoOutcome::Audio(output_byte)) => {
+                record_success("hide_message", &format, lsb_deep, input_len, start.elapsed());
+                Ok(Response::new(Box::pin(stream_file_as_chunks(
+                    output_byte,
+                    CHUNK_SIZE,
+                    0,
+                ))))
+            }
+            Ok(StegoOutcome::Message(_)) => unreachable!("Hide always yields audio"),
+            Err(err) => {
+                record_failure("hide_message", &format, lsb_deep, command_failure_kind(&err));
+                Err(command_to_status(err))
+            }
+        }
     }
 
     async fn extract_message(
         &self,
-        request: Request<ExtractRequest>,
+        request: Request<Streaming<ExtractRequest>>,
     ) -> Result<Response<MessageResponse>, Status> {
-        let ExtractRequest {
-            file,
+        let start = Instant::now();
+        let (file, metadata) =
+            aggregate_file_from_stream(request.into_inner(), self.pending_uploads.as_ref()).await?;
+        let AudioMetadata::General {
             password,
             format,
             lsb_deep,
-        } = request.into_inner();
-        let stego = get_stego!(format, lsb_deep, self.settings);
-
-        let (samples, _spec) = stego
-            .read_samples_from_byte(file)
-            .map_err(|err| Status::internal(err.to_string()))?;
+        } = metadata
+        else {
+            return Err(Status::invalid_argument(
+                "Expected metadata in the first frame",
+            ));
+        };
+        let input_len = file.len();
+        let lsb_deep = lsb_deep as u8;
 
-        let message = stego
-            .extract_message_binary(&samples, &password)
-            .map_err(|err| Status::internal(err.to_string()))?;
+        let command = StegoCommand::Extract {
+            file,
+            password,
+            format: format.clone(),
+            lsb_deep,
+        };
 
-        let reply = MessageResponse { message };
-        Ok(Response::new(reply))
+        match execute(command, (*self.settings).clone()) {
+            Ok(StegoOutcome::Message(message)) => {
+                record_success(
+                    "extract_message",
+                    &format,
+                    lsb_deep,
+                    input_len,
+                    start.elapsed(),
+                );
+                Ok(Response::new(MessageResponse { message }))
+            }
+            Ok(StegoOutcome::Audio(_)) => unreachable!("Extract always yields a message"),
+            Err(err) => {
+                record_failure(
+                    "extract_message",
+                    &format,
+                    lsb_deep,
+                    command_failure_kind(&err),
+                );
+                Err(command_to_status(err))
+            }
+        }
     }
 
     async fn clear_message(
         &self,
-        request: Request<ClearRequest>,
-    ) -> Result<Response<AudioResponse>, Status> {
-        let ClearRequest {
-            file,
+        request: Request<Streaming<ClearRequest>>,
+    ) -> Result<Response<Self::ClearMessageStream>, Status> {
+        let start = Instant::now();
+        let (file, metadata) =
+            aggregate_file_from_stream(request.into_inner(), self.pending_uploads.as_ref()).await?;
+        let AudioMetadata::General {
             password,
             format,
             lsb_deep,
-        } = request.into_inner();
-        let stego = get_stego!(format, lsb_deep, self.settings);
+        } = metadata
+        else {
+            return Err(Status::invalid_argument(
+                "Expected metadata in the first frame",
+            ));
+        };
+        let input_len = file.len();
+        let lsb_deep = lsb_deep as u8;
 
-        let (mut samples, spec) = stego
-            .read_samples_from_byte(file)
-            .map_err(|err| Status::internal(err.to_string()))?;
+        let command = StegoCommand::Clear {
+            file,
+            password,
+            format: format.clone(),
+            lsb_deep,
+        };
 
-        stego
-            .clear_secret_message_binary(&mut samples, &password)
-            .map_err(|err| Status::internal(err.to_string()))?;
+        match execute(command, (*self.settings).clone()) {
+            Ok(StegoOutcome::Audio(output_byte)) => {
+                record_success("clear_message", &format, lsb_deep, input_len, start.elapsed());
+                Ok(Response::new(Box::pin(stream_file_as_chunks(
+                    output_byte,
+                    CHUNK_SIZE,
+                    0,
+                ))))
+            }
+            Ok(StegoOutcome::Message(_)) => unreachable!("Clear always yields audio"),
+            Err(err) => {
+                record_failure("clear_message", &format, lsb_deep, command_failure_kind(&err));
+                Err(command_to_status(err))
+            }
+        }
+    }
+
+    /// Streams back audio previously registered with the share store by a caller that
+    /// opted in to deferred delivery, consuming the share token in the process.
+    async fn fetch_result(
+        &self,
+        request: Request<FetchResultRequest>,
+    ) -> Result<Response<Self::FetchResultStream>, Status> {
+        let FetchResultRequest { token } = request.into_inner();
+
+        match self.share_store.take(&token) {
+            Some(bytes) => Ok(Response::new(Box::pin(stream_file_as_chunks(
+                bytes, CHUNK_SIZE, 0,
+            )))),
+            None => Err(Status::not_found("Share link not found or expired")),
+        }
+    }
+
+    async fn capacity(
+        &self,
+        request: Request<CapacityRequest>,
+    ) -> Result<Response<CapacityResponse>, Status> {
+        let CapacityRequest {
+            file,
+            format,
+            lsb_deep,
+        } = request.into_inner();
+        let stego = get_stego!("capacity", format, lsb_deep, false, self.settings);
 
-        let output_byte = stego
-            .write_samples_to_byte(spec, &samples)
+        let (capacity_bytes, overhead_bytes) = stego
+            .read_capacity_from_byte(file)
             .map_err(|err| Status::internal(err.to_string()))?;
 
-        Ok(Response::new(AudioResponse { file: output_byte }))
+        Ok(Response::new(CapacityResponse {
+            capacity_bytes: capacity_bytes as u64,
+            overhead_bytes: overhead_bytes as u64,
+        }))
     }
 }
@@ -1,3 +1,4 @@
+use crate::services::pending_upload::{MergeError, MergeOutcome, PendingUploadBackend};
 use crate::stego_wave_grpc::{AudioResponse, ClearRequest, ExtractRequest, HideRequest};
 use bytes::Bytes;
 use std::mem;
@@ -12,6 +13,8 @@ pub enum AudioMetadata {
         password: String,
         format: String,
         lsb_deep: u32,
+        compress: bool,
+        encrypt: bool,
     },
     General {
         password: String,
@@ -22,6 +25,9 @@ pub enum AudioMetadata {
 
 pub trait AudioRequestExt {
     fn get_file(&self) -> &[u8];
+    fn get_offset(&self) -> u64;
+    fn get_total_len(&self) -> u64;
+    fn get_upload_id(&self) -> &str;
     fn get_metadata(&mut self) -> AudioMetadata;
 }
 
@@ -29,12 +35,23 @@ impl AudioRequestExt for HideRequest {
     fn get_file(&self) -> &[u8] {
         &self.file
     }
+    fn get_offset(&self) -> u64 {
+        self.offset
+    }
+    fn get_total_len(&self) -> u64 {
+        self.total_len
+    }
+    fn get_upload_id(&self) -> &str {
+        &self.upload_id
+    }
     fn get_metadata(&mut self) -> AudioMetadata {
         AudioMetadata::Hide {
             message: mem::take(&mut self.message),
             password: mem::take(&mut self.password),
             format: mem::take(&mut self.format),
             lsb_deep: self.lsb_deep,
+            compress: self.compress,
+            encrypt: self.encrypt,
         }
     }
 }
@@ -43,6 +60,15 @@ impl AudioRequestExt for ExtractRequest {
     fn get_file(&self) -> &[u8] {
         &self.file
     }
+    fn get_offset(&self) -> u64 {
+        self.offset
+    }
+    fn get_total_len(&self) -> u64 {
+        self.total_len
+    }
+    fn get_upload_id(&self) -> &str {
+        &self.upload_id
+    }
     fn get_metadata(&mut self) -> AudioMetadata {
         AudioMetadata::General {
             password: mem::take(&mut self.password),
@@ -56,6 +82,15 @@ impl AudioRequestExt for ClearRequest {
     fn get_file(&self) -> &[u8] {
         &self.file
     }
+    fn get_offset(&self) -> u64 {
+        self.offset
+    }
+    fn get_total_len(&self) -> u64 {
+        self.total_len
+    }
+    fn get_upload_id(&self) -> &str {
+        &self.upload_id
+    }
     fn get_metadata(&mut self) -> AudioMetadata {
         AudioMetadata::General {
             password: mem::take(&mut self.password),
@@ -65,46 +100,96 @@ impl AudioRequestExt for ClearRequest {
     }
 }
 
+/// Reassembles the file carried across `stream`'s chunk frames, merging each one
+/// into `pending_uploads` under the upload_id it carries.
+///
+/// Chunks are tracked by their offset in the reassembled file rather than assumed
+/// to arrive in order, so a reordered or duplicated chunk is caught as an overlap
+/// (`Status::invalid_argument`) instead of silently corrupting the file. A chunk
+/// declaring a nonzero `total_len` that the stream ends short of doesn't fail the
+/// call outright: the partial session is left registered and reported back via
+/// `Status::aborted` so a later call can resume it with the same upload_id,
+/// starting from the offset the message names, instead of resending everything.
 pub async fn aggregate_file_from_stream<T>(
     mut stream: tonic::Streaming<T>,
+    pending_uploads: &dyn PendingUploadBackend,
 ) -> Result<(Vec<u8>, AudioMetadata), Status>
 where
     T: AudioRequestExt + Send + 'static,
 {
-    let mut file: Vec<u8> = Vec::new();
-    let mut metadata: Option<AudioMetadata> = None;
+    let mut upload_id: Option<String> = None;
+    let mut last_received_len = 0u64;
 
     while let Some(chunk) = stream.next().await {
         let mut chunk = chunk?;
-        if metadata.is_none() {
-            metadata = Some(chunk.get_metadata());
+        let this_upload_id = chunk.get_upload_id().to_string();
+        let offset = chunk.get_offset();
+        let total_len = chunk.get_total_len();
+        // Only the very first frame of the very first attempt (offset 0) carries
+        // real metadata; a resumed attempt's first frame starts at a nonzero
+        // offset and leaves it blank, since the server already has it.
+        let metadata = if offset == 0 {
+            Some(chunk.get_metadata())
+        } else {
+            None
+        };
+        let bytes = chunk.get_file();
+
+        match pending_uploads.merge(&this_upload_id, offset, total_len, bytes, metadata) {
+            Ok(MergeOutcome::Complete { file, metadata }) => return Ok((file, metadata)),
+            Ok(MergeOutcome::Incomplete { received_len }) => {
+                upload_id = Some(this_upload_id);
+                last_received_len = received_len;
+            }
+            Err(MergeError::Overlap) => {
+                return Err(Status::invalid_argument(
+                    "Chunk overlaps a byte range already received for this upload_id.",
+                ));
+            }
+            Err(MergeError::MissingMetadata) => {
+                return Err(Status::invalid_argument(
+                    "Metadata not received: at least one packet with information is expected.",
+                ));
+            }
+            Err(MergeError::TooLarge) => {
+                return Err(Status::invalid_argument(
+                    "Chunk's offset or total_len exceeds the server's configured upload limit.",
+                ));
+            }
         }
-        file.extend_from_slice(chunk.get_file());
     }
 
-    let metadata = metadata.ok_or(Status::invalid_argument(
-        "Metadata not received: at least one packet with information is expected.",
-    ))?;
+    let upload_id = upload_id.ok_or_else(|| Status::invalid_argument("Empty request stream."))?;
+
+    if let Some((file, metadata)) = pending_uploads.finalize(&upload_id) {
+        return Ok((file, metadata));
+    }
 
-    Ok((file, metadata))
+    Err(Status::aborted(format!(
+        "Upload incomplete: received {last_received_len} contiguous byte(s). Resume by reopening \
+         the stream with upload_id '{upload_id}' and the remaining bytes starting at offset {last_received_len}."
+    )))
 }
 
 pub fn stream_file_as_chunks(
     file: Vec<u8>,
     chunk_size: usize,
+    start_offset: usize,
 ) -> impl futures::Stream<Item = Result<AudioResponse, Status>> {
     let full_bytes = Bytes::from(file);
     let total_len = full_bytes.len();
     let (tx, rx) = mpsc::channel(4);
 
     tokio::spawn(async move {
-        let mut start = 0;
+        let mut start = start_offset.min(total_len);
         while start < total_len {
             let end = std::cmp::min(start + chunk_size, total_len);
             let chunk = full_bytes.slice(start..end);
 
             let resp = AudioResponse {
                 file: chunk.to_vec(),
+                offset: start as u64,
+                total_len: total_len as u64,
             };
             if tx.send(Ok(resp)).await.is_err() {
                 break;
@@ -1,19 +1,97 @@
+use crate::metrics_server::run_metrics_server;
 use crate::services;
+use crate::services::PendingUploadBackend;
 use crate::stego_wave_grpc::stego_wave_service_server::StegoWaveServiceServer;
+use metrics_exporter_prometheus::PrometheusHandle;
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use stego_wave::auth::TokenAuthority;
 use stego_wave::configuration::StegoWaveLib;
-use tonic::transport::Server;
+use stego_wave::share::ShareBackend;
+use stego_wave::tls::TlsMaterial;
+use tonic::codec::CompressionEncoding;
+use tonic::service::interceptor::InterceptedService;
+use tonic::transport::{Identity, Server, ServerTlsConfig};
+use tonic::{Request, Status};
 
 const MAX_MESSAGE_SIZE: usize = 100 * 1024 * 1024;
 
+/// How often the share store and pending-upload store are swept for expired entries.
+const EXPIRY_SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
 pub fn run_server(
     addr: SocketAddr,
+    metrics_addr: SocketAddr,
     settings: StegoWaveLib,
+    auth: Arc<TokenAuthority>,
+    metrics_handle: PrometheusHandle,
+    tls: Option<TlsMaterial>,
+    share_store: Arc<dyn ShareBackend>,
+    pending_uploads: Arc<dyn PendingUploadBackend>,
 ) -> impl Future<Output = Result<(), tonic::transport::Error>> {
-    let stego_wave_service = services::StegoWaveServiceImpl::new(settings);
+    spawn_share_sweeper(share_store.clone());
+    spawn_pending_upload_sweeper(pending_uploads.clone());
+    tokio::spawn(run_metrics_server(metrics_addr, metrics_handle));
+
+    // Audio carriers compress well and are often large, so negotiate gzip on every
+    // streamed chunk; tonic only actually compresses a call when the peer advertised
+    // support for it, so this is a no-op against an older client that didn't.
+    let stego_wave_service =
+        services::StegoWaveServiceImpl::new(settings, share_store, pending_uploads);
     let svc = StegoWaveServiceServer::new(stego_wave_service)
         .max_encoding_message_size(MAX_MESSAGE_SIZE)
-        .max_decoding_message_size(MAX_MESSAGE_SIZE);
+        .max_decoding_message_size(MAX_MESSAGE_SIZE)
+        .send_compressed(CompressionEncoding::Gzip)
+        .accept_compressed(CompressionEncoding::Gzip);
+    let svc = InterceptedService::new(svc, auth_interceptor(auth));
+
+    let mut builder = Server::builder();
+    if let Some(material) = tls {
+        let identity = Identity::from_pem(material.cert_pem, material.key_pem);
+        builder = builder
+            .tls_config(ServerTlsConfig::new().identity(identity))
+            .expect("invalid TLS configuration");
+    }
+
+    builder.add_service(svc).serve(addr)
+}
+
+/// Rejects requests that do not carry a `authorization: Bearer <token>` metadata
+/// entry naming a known token.
+fn auth_interceptor(
+    auth: Arc<TokenAuthority>,
+) -> impl FnMut(Request<()>) -> Result<Request<()>, Status> + Clone {
+    move |req: Request<()>| {
+        let token = req
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+
+        match token {
+            Some(token) if auth.authorize(token) => Ok(req),
+            _ => Err(Status::unauthenticated(
+                "Missing or invalid authorization token",
+            )),
+        }
+    }
+}
+
+fn spawn_share_sweeper(share_store: Arc<dyn ShareBackend>) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(EXPIRY_SWEEP_INTERVAL).await;
+            share_store.sweep_expired();
+        }
+    });
+}
 
-    Server::builder().add_service(svc).serve(addr)
+fn spawn_pending_upload_sweeper(pending_uploads: Arc<dyn PendingUploadBackend>) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(EXPIRY_SWEEP_INTERVAL).await;
+            pending_uploads.sweep_expired();
+        }
+    });
 }
@@ -1,10 +1,17 @@
 use reqwest::Client;
 use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
 use stego_wave::error::StegoWaveClientError;
 use url::Url;
 
 use stego_wave::object::StegoWaveClient;
 
+#[derive(Deserialize)]
+struct CapacityResponse {
+    capacity_bytes: usize,
+    overhead_bytes: usize,
+}
+
 fn convert_reqwest_error(err: reqwest::Error) -> StegoWaveClientError {
     if err.is_connect() {
         StegoWaveClientError::ConnectionFailed
@@ -26,6 +33,40 @@ impl StegoWaveRestClient {
             client: Client::new(),
         })
     }
+
+    /// Fetches a share link's audio bytes, optionally as a `Range: bytes=start-end`
+    /// request so a caller can resume a download that was cut off partway through
+    /// instead of starting over. `range` is `(start, end)` inclusive, matching the
+    /// `Content-Range` the server echoes back; pass `None` to fetch the whole body.
+    ///
+    /// Note that `/share/{token}` tokens are one-shot (see `ShareStore::take`), so
+    /// this only supports resuming a single interrupted transfer, not fetching
+    /// disjoint ranges of the same token across multiple requests.
+    pub async fn fetch_share_range(
+        &self,
+        token: &str,
+        range: Option<(u64, u64)>,
+    ) -> Result<Vec<u8>, StegoWaveClientError> {
+        let url = self
+            .rest_url
+            .join(&format!("share/{token}"))
+            .map_err(|err| StegoWaveClientError::UlrInvalid(err.to_string()))?;
+
+        let mut request = self.client.get(url);
+        if let Some((start, end)) = range {
+            request = request.header("Range", format!("bytes={start}-{end}"));
+        }
+
+        let response = request.send().await.map_err(convert_reqwest_error)?;
+
+        if !response.status().is_success() {
+            let err_text = response.text().await.map_err(convert_reqwest_error)?;
+            return Err(StegoWaveClientError::Response(err_text));
+        }
+
+        let bytes = response.bytes().await.map_err(convert_reqwest_error)?;
+        Ok(bytes.to_vec())
+    }
 }
 
 #[async_trait::async_trait]
@@ -37,13 +78,17 @@ impl StegoWaveClient for StegoWaveRestClient {
         password: String,
         format: String,
         lsb_deep: u8,
+        compress: bool,
+        encrypt: bool,
     ) -> Result<Vec<u8>, StegoWaveClientError> {
         let form = Form::new()
             .part("file", Part::bytes(file))
             .text("message", message)
             .text("password", password)
             .text("format", format)
-            .text("lsb_deep", lsb_deep.to_string());
+            .text("lsb_deep", lsb_deep.to_string())
+            .text("compress", compress.to_string())
+            .text("encrypt", encrypt.to_string());
 
         let url = self
             .rest_url
@@ -134,4 +179,37 @@ impl StegoWaveClient for StegoWaveRestClient {
         let bytes = response.bytes().await.map_err(convert_reqwest_error)?;
         Ok(bytes.to_vec())
     }
+
+    async fn capacity(
+        &mut self,
+        file: Vec<u8>,
+        format: String,
+        lsb_deep: u8,
+    ) -> Result<(usize, usize), StegoWaveClientError> {
+        let form = Form::new()
+            .part("file", Part::bytes(file))
+            .text("format", format)
+            .text("lsb_deep", lsb_deep.to_string());
+
+        let url = self
+            .rest_url
+            .join("api/capacity")
+            .map_err(|err| StegoWaveClientError::UlrInvalid(err.to_string()))?;
+
+        let response = self
+            .client
+            .post(url)
+            .multipart(form)
+            .send()
+            .await
+            .map_err(convert_reqwest_error)?;
+
+        if !response.status().is_success() {
+            let err_text = response.text().await.map_err(convert_reqwest_error)?;
+            return Err(StegoWaveClientError::Response(err_text));
+        }
+
+        let capacity: CapacityResponse = response.json().await.map_err(convert_reqwest_error)?;
+        Ok((capacity.capacity_bytes, capacity.overhead_bytes))
+    }
 }
@@ -0,0 +1,87 @@
+use actix_web::HttpResponse;
+use actix_web::http::header::HeaderMap;
+
+/// Result of matching an incoming `Range` header against a body of `total` bytes.
+enum RangeMatch {
+    /// No `Range` header, or one this doesn't know how to satisfy: serve the full body.
+    Full,
+    /// A satisfiable single `bytes=start-end` range.
+    Partial { start: usize, end: usize },
+    /// `Range` named a start past the end of the body.
+    NotSatisfiable,
+}
+
+/// Parses a single-range `Range: bytes=start-end` header against a body of `total`
+/// bytes, clamping `end` to `total - 1`. Multi-range requests (`bytes=0-10,20-30`),
+/// other units, and malformed syntax all fall back to [`RangeMatch::Full`], the same
+/// as having no header at all.
+fn parse_range(headers: &HeaderMap, total: usize) -> RangeMatch {
+    let Some(value) = headers
+        .get(actix_web::http::header::RANGE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return RangeMatch::Full;
+    };
+
+    let Some(spec) = value.strip_prefix("bytes=") else {
+        return RangeMatch::Full;
+    };
+
+    if spec.contains(',') {
+        return RangeMatch::Full;
+    }
+
+    let Some((start, end)) = spec.split_once('-') else {
+        return RangeMatch::Full;
+    };
+
+    let Ok(start) = start.parse::<usize>() else {
+        return RangeMatch::Full;
+    };
+
+    if start >= total {
+        return RangeMatch::NotSatisfiable;
+    }
+
+    let end = if end.is_empty() {
+        total - 1
+    } else {
+        match end.parse::<usize>() {
+            Ok(end) => end.min(total - 1),
+            Err(_) => return RangeMatch::Full,
+        }
+    };
+
+    if end < start {
+        return RangeMatch::Full;
+    }
+
+    RangeMatch::Partial { start, end }
+}
+
+/// Serves `body` as an `audio/wav` attachment named `filename`, honoring a `Range`
+/// header on `headers` when present: a satisfiable `bytes=start-end` range gets back
+/// `206 Partial Content` with `Content-Range` and just that slice, an unsatisfiable
+/// one gets `416 Range Not Satisfiable`, and anything else falls back to the full
+/// `200 OK` body. Shared by every endpoint that advertises `Accept-Ranges: bytes`.
+pub fn ranged_audio_response(headers: &HeaderMap, body: Vec<u8>, filename: &str) -> HttpResponse {
+    let total = body.len();
+    let disposition = format!("attachment; filename=\"{filename}\"");
+
+    match parse_range(headers, total) {
+        RangeMatch::Full => HttpResponse::Ok()
+            .append_header(("Accept-Ranges", "bytes"))
+            .append_header(("Content-Disposition", disposition))
+            .content_type("audio/wav")
+            .body(body),
+        RangeMatch::Partial { start, end } => HttpResponse::PartialContent()
+            .append_header(("Accept-Ranges", "bytes"))
+            .append_header(("Content-Range", format!("bytes {start}-{end}/{total}")))
+            .append_header(("Content-Disposition", disposition))
+            .content_type("audio/wav")
+            .body(body[start..=end].to_vec()),
+        RangeMatch::NotSatisfiable => HttpResponse::RangeNotSatisfiable()
+            .append_header(("Content-Range", format!("bytes */{total}")))
+            .finish(),
+    }
+}
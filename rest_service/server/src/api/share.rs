@@ -0,0 +1,28 @@
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
+use std::sync::Arc;
+use stego_wave::share::ShareBackend;
+
+use crate::api::range::ranged_audio_response;
+
+/// Fetches the audio bytes registered under `token` and removes them from the store.
+///
+/// The token is one-shot: a second request for the same token, or one made after the
+/// entry's expiry, gets a 404. There is no bearer-token check here (see
+/// `require_auth` in `startup.rs`) since the whole point of a share link is that it
+/// can be handed to a browser's native downloader without smuggling in an
+/// `Authorization` header.
+///
+/// Honors a `Range` header the same way `hide_message`/`clear_message` do, but since
+/// `take` consumes the entry on first access, a client that splits a download into
+/// several range requests only gets the first one satisfied; resuming a cut-off
+/// transfer works, fetching disjoint ranges across multiple requests does not.
+pub async fn fetch_share(
+    req: HttpRequest,
+    token: web::Path<String>,
+    store: web::Data<Arc<dyn ShareBackend>>,
+) -> impl Responder {
+    match store.take(&token.into_inner()) {
+        Some(bytes) => ranged_audio_response(req.headers(), bytes, "stego_output.wav"),
+        None => HttpResponse::NotFound().body("Share link not found or expired"),
+    }
+}
@@ -1,45 +1,66 @@
 use actix_multipart::Multipart;
-use actix_web::{HttpResponse, Responder, post, web};
-use stego_wave::AudioSteganography;
+use actix_web::{HttpRequest, HttpResponse, Responder, post, web};
+use std::sync::Arc;
+use std::time::Instant;
 use stego_wave::configuration::StegoWaveLib;
 use stego_wave::error::StegoError;
 use stego_wave::formats::get_stego_by_str;
+use stego_wave::metrics::{FailureKind, failure_kind_for, record_failure, record_success};
+use stego_wave::share::ShareBackend;
 
-use crate::models::request_object::{ClearRequest, ExtractRequest, HideRequest};
-use crate::services::stego_wave::parse_multipart_payload;
+use crate::api::range::ranged_audio_response;
+use crate::models::request_object::{
+    CapacityRequest, CapacityResponse, ClearRequest, ExtractRequest, HideRequest, ShareResponse,
+};
+use crate::services::stego_wave::{UploadError, parse_multipart_payload};
 
 macro_rules! audio_response_from_samples {
-    ($stego:expr, $spec:expr, $samples:expr) => {{
+    ($operation:expr, $format:expr, $lsb_deep:expr, $start:expr, $input_len:expr, $stego:expr, $spec:expr, $samples:expr, $as_share:expr, $share_store:expr, $req:expr) => {{
         let out_buf = match $stego.write_samples_to_byte($spec, &$samples) {
             Ok(buf) => buf,
-            Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
+            Err(err) => {
+                record_failure($operation, &$format, $lsb_deep, FailureKind::Internal);
+                return HttpResponse::InternalServerError().body(err.to_string());
+            }
         };
 
-        HttpResponse::Ok()
-            .append_header(("Accept-Ranges", "bytes"))
-            .append_header((
-                "Content-Disposition",
-                format!("attachment; filename=\"{}\"", $stego.default_filename()),
-            ))
-            .content_type("audio/wav")
-            .body(out_buf)
+        record_success($operation, &$format, $lsb_deep, $input_len, $start.elapsed());
+
+        if $as_share {
+            let token = $share_store.register(out_buf);
+            let url = format!("/share/{token}");
+            return HttpResponse::Ok().json(ShareResponse { token, url });
+        }
+
+        ranged_audio_response($req.headers(), out_buf, &$stego.default_filename())
     }};
 }
 
 macro_rules! parse_form {
-    ($payload:expr) => {
-        match parse_multipart_payload($payload).await {
+    ($payload:expr, $max_upload_bytes:expr) => {
+        match parse_multipart_payload($payload, $max_upload_bytes).await {
             Ok(data) => data,
-            Err(err) => return HttpResponse::InternalServerError().body(err),
+            Err(UploadError::TooLarge) => {
+                return HttpResponse::PayloadTooLarge().body(UploadError::TooLarge.to_string());
+            }
+            Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
         }
     };
 }
 
 macro_rules! get_stego {
-    ($format:expr, $lsb_deep:expr, $settings:expr) => {
-        match get_stego_by_str(&$format, $lsb_deep as _, (*$settings.into_inner()).clone()) {
+    ($operation:expr, $format:expr, $lsb_deep:expr, $compress:expr, $settings:expr) => {
+        match get_stego_by_str(
+            &$format,
+            $lsb_deep as _,
+            $compress,
+            (*$settings.into_inner()).clone(),
+        ) {
             Ok(stego) => stego,
-            Err(err) => return HttpResponse::BadRequest().body(err.to_string()),
+            Err(err) => {
+                record_failure($operation, &$format, $lsb_deep, FailureKind::InvalidArgument);
+                return HttpResponse::BadRequest().body(err.to_string());
+            }
         }
     };
 }
@@ -59,27 +80,75 @@ macro_rules! get_stego {
     )
 )]
 #[post("/api/hide_message")]
-pub async fn hide_message(payload: Multipart, settings: web::Data<StegoWaveLib>) -> impl Responder {
-    let multipart = parse_form!(payload);
+pub async fn hide_message(
+    req: HttpRequest,
+    payload: Multipart,
+    settings: web::Data<StegoWaveLib>,
+    share_store: web::Data<Arc<dyn ShareBackend>>,
+    max_upload_bytes: web::Data<u64>,
+) -> impl Responder {
+    let start = Instant::now();
+    let multipart = parse_form!(payload, *max_upload_bytes.get_ref());
     let hide_request: HideRequest = match multipart.try_into() {
         Ok(req) => req,
         Err(err) => return HttpResponse::BadRequest().body(err),
     };
 
-    let stego = get_stego!(hide_request.format, hide_request.lsb_deep, settings);
+    let stego = get_stego!(
+        "hide_message",
+        hide_request.format,
+        hide_request.lsb_deep,
+        hide_request.compress,
+        settings
+    );
+    let input_len = hide_request.file.len();
 
     let (mut samples, spec) = match stego.read_samples_from_byte(hide_request.file) {
         Ok(data) => data,
-        Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
+        Err(err) => {
+            record_failure(
+                "hide_message",
+                &hide_request.format,
+                hide_request.lsb_deep,
+                failure_kind_for(&err),
+            );
+            return HttpResponse::InternalServerError().body(err.to_string());
+        }
     };
 
-    if let Err(err) =
-        stego.hide_message_binary(&mut samples, hide_request.message, hide_request.password)
-    {
-        return HttpResponse::InternalServerError().body(err.to_string());
+    if let Err(err) = stego.hide_message_binary(
+        &mut samples,
+        hide_request.message,
+        hide_request.password,
+        hide_request.encrypt,
+    ) {
+        record_failure(
+            "hide_message",
+            &hide_request.format,
+            hide_request.lsb_deep,
+            failure_kind_for(&err),
+        );
+        return match err {
+            StegoError::IncorrectPassword
+            | StegoError::IntegrityCheckFailed
+            | StegoError::NotEnoughSamples(_) => HttpResponse::BadRequest().body(err.to_string()),
+            err => HttpResponse::InternalServerError().body(err.to_string()),
+        };
     }
 
-    audio_response_from_samples!(stego, spec, samples)
+    audio_response_from_samples!(
+        "hide_message",
+        hide_request.format,
+        hide_request.lsb_deep,
+        start,
+        input_len,
+        stego,
+        spec,
+        samples,
+        hide_request.as_share,
+        share_store,
+        req
+    )
 }
 
 #[utoipa::path(
@@ -100,26 +169,62 @@ pub async fn hide_message(payload: Multipart, settings: web::Data<StegoWaveLib>)
 pub async fn extract_message(
     payload: Multipart,
     settings: web::Data<StegoWaveLib>,
+    max_upload_bytes: web::Data<u64>,
 ) -> impl Responder {
-    let multipart = parse_form!(payload);
+    let start = Instant::now();
+    let multipart = parse_form!(payload, *max_upload_bytes.get_ref());
     let extract_request: ExtractRequest = match multipart.try_into() {
         Ok(req) => req,
         Err(err) => return HttpResponse::BadRequest().body(err),
     };
 
-    let stego = get_stego!(extract_request.format, extract_request.lsb_deep, settings);
+    let stego = get_stego!(
+        "extract_message",
+        extract_request.format,
+        extract_request.lsb_deep,
+        false,
+        settings
+    );
+    let input_len = extract_request.file.len();
 
     let (samples, _) = match stego.read_samples_from_byte(extract_request.file) {
         Ok(data) => data,
-        Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
+        Err(err) => {
+            record_failure(
+                "extract_message",
+                &extract_request.format,
+                extract_request.lsb_deep,
+                failure_kind_for(&err),
+            );
+            return HttpResponse::InternalServerError().body(err.to_string());
+        }
     };
 
     match stego.extract_message_binary(&samples, extract_request.password) {
-        Ok(msg) => HttpResponse::Ok().body(msg),
-        Err(StegoError::IncorrectPassword) => {
-            HttpResponse::BadRequest().body(StegoError::IncorrectPassword.to_string())
+        Ok(msg) => {
+            record_success(
+                "extract_message",
+                &extract_request.format,
+                extract_request.lsb_deep,
+                input_len,
+                start.elapsed(),
+            );
+            HttpResponse::Ok().body(msg)
+        }
+        Err(err) => {
+            record_failure(
+                "extract_message",
+                &extract_request.format,
+                extract_request.lsb_deep,
+                failure_kind_for(&err),
+            );
+            match err {
+                StegoError::IncorrectPassword | StegoError::IntegrityCheckFailed => {
+                    HttpResponse::BadRequest().body(err.to_string())
+                }
+                err => HttpResponse::InternalServerError().body(err.to_string()),
+            }
         }
-        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
     }
 }
 
@@ -139,35 +244,120 @@ pub async fn extract_message(
 )]
 #[post("/api/clear_message")]
 pub async fn clear_message(
+    req: HttpRequest,
     payload: Multipart,
     settings: web::Data<StegoWaveLib>,
+    share_store: web::Data<Arc<dyn ShareBackend>>,
+    max_upload_bytes: web::Data<u64>,
 ) -> impl Responder {
-    let multipart = parse_form!(payload);
+    let start = Instant::now();
+    let multipart = parse_form!(payload, *max_upload_bytes.get_ref());
     let clear_request: ClearRequest = match multipart.try_into() {
         Ok(req) => req,
         Err(err) => return HttpResponse::BadRequest().body(err),
     };
 
-    let stego = get_stego!(clear_request.format, clear_request.lsb_deep, settings);
+    let stego = get_stego!(
+        "clear_message",
+        clear_request.format,
+        clear_request.lsb_deep,
+        false,
+        settings
+    );
+    let input_len = clear_request.file.len();
 
     let (mut samples, spec) = match stego.read_samples_from_byte(clear_request.file) {
         Ok(data) => data,
-        Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
+        Err(err) => {
+            record_failure(
+                "clear_message",
+                &clear_request.format,
+                clear_request.lsb_deep,
+                failure_kind_for(&err),
+            );
+            return HttpResponse::InternalServerError().body(err.to_string());
+        }
     };
 
     match stego.clear_secret_message_binary(&mut samples, clear_request.password) {
         Ok(()) => {}
-        Err(StegoError::IncorrectPassword) => {
-            return HttpResponse::BadRequest().body(StegoError::IncorrectPassword.to_string());
+        Err(err) => {
+            record_failure(
+                "clear_message",
+                &clear_request.format,
+                clear_request.lsb_deep,
+                failure_kind_for(&err),
+            );
+            return match err {
+                StegoError::IncorrectPassword | StegoError::IntegrityCheckFailed => {
+                    HttpResponse::BadRequest().body(err.to_string())
+                }
+                err => HttpResponse::InternalServerError().body(err.to_string()),
+            };
         }
-        Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
     }
 
-    audio_response_from_samples!(stego, spec, samples)
+    audio_response_from_samples!(
+        "clear_message",
+        clear_request.format,
+        clear_request.lsb_deep,
+        start,
+        input_len,
+        stego,
+        spec,
+        samples,
+        clear_request.as_share,
+        share_store,
+        req
+    )
+}
+
+#[utoipa::path(
+    post,
+    tag = "StegoWave",
+    path = "/api/capacity",
+    request_body (
+        content = CapacityRequest,
+        content_type = "multipart/form-data"
+    ),
+    responses (
+        (status = 200, description = "Returns the carrier's spare capacity", content_type = "application/json", body = CapacityResponse),
+        (status = 400, description = "Bad request, missing required fields", content_type = "text/plain"),
+        (status = 500, description = "Internal server error", content_type = "text/plain")
+    )
+)]
+#[post("/api/capacity")]
+pub async fn capacity(
+    payload: Multipart,
+    settings: web::Data<StegoWaveLib>,
+    max_upload_bytes: web::Data<u64>,
+) -> impl Responder {
+    let multipart = parse_form!(payload, *max_upload_bytes.get_ref());
+    let capacity_request: CapacityRequest = match multipart.try_into() {
+        Ok(req) => req,
+        Err(err) => return HttpResponse::BadRequest().body(err),
+    };
+
+    let stego = get_stego!(
+        "capacity",
+        capacity_request.format,
+        capacity_request.lsb_deep,
+        false,
+        settings
+    );
+
+    match stego.read_capacity_from_byte(capacity_request.file) {
+        Ok((capacity_bytes, overhead_bytes)) => HttpResponse::Ok().json(CapacityResponse {
+            capacity_bytes,
+            overhead_bytes,
+        }),
+        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+    }
 }
 
 pub fn routers(cfg: &mut web::ServiceConfig) {
     cfg.service(hide_message)
         .service(extract_message)
-        .service(clear_message);
+        .service(clear_message)
+        .service(capacity);
 }
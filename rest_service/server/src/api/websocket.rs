@@ -0,0 +1,70 @@
+use actix_web::{Error, HttpRequest, HttpResponse, web};
+use actix_ws::Message;
+use stego_wave::command::{CommandRequest, StegoOutcome, execute};
+use stego_wave::configuration::StegoWaveLib;
+use tokio_stream::StreamExt;
+
+/// Accepts a JSON control frame describing the operation, followed by one or more
+/// binary frames carrying the audio file, then runs it through the same
+/// [`stego_wave::command::execute`] pipeline the gRPC and Unix-socket gateways use.
+///
+/// This is the server half of the `WebSocket` transport (see `ws_client` for the
+/// client half and `StegoWaveServer::WebSocket` in the CLI). It lives here, on the
+/// REST service's actix-web app and `TcpListener`, rather than as a standalone
+/// `ws_server` crate/port: actix-ws sessions need an HTTP upgrade handshake, which
+/// this crate already serves, so reusing it avoids standing up a second listener
+/// for what is otherwise an independent transport.
+pub async fn stego_ws(
+    req: HttpRequest,
+    stream: web::Payload,
+    settings: web::Data<StegoWaveLib>,
+) -> Result<HttpResponse, Error> {
+    let (response, mut session, mut msg_stream) = actix_ws::handle(&req, stream)?;
+
+    actix_web::rt::spawn(async move {
+        let mut control: Option<CommandRequest> = None;
+        let mut file = Vec::new();
+
+        while let Some(Ok(msg)) = msg_stream.next().await {
+            match msg {
+                Message::Text(text) if control.is_none() => match serde_json::from_str(&text) {
+                    Ok(frame) => control = Some(frame),
+                    Err(err) => {
+                        let _ = session.text(format!("Invalid control frame: {err}")).await;
+                        let _ = session.close(None).await;
+                        return;
+                    }
+                },
+                Message::Binary(bytes) => file.extend_from_slice(&bytes),
+                Message::Close(reason) => {
+                    let _ = session.close(reason).await;
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        let Some(control) = control else {
+            let _ = session.text("Missing control frame").await;
+            let _ = session.close(None).await;
+            return;
+        };
+
+        let command = control.into_command(file);
+        match execute(command, (*settings.into_inner()).clone()) {
+            Ok(StegoOutcome::Audio(bytes)) => {
+                let _ = session.binary(bytes).await;
+            }
+            Ok(StegoOutcome::Message(message)) => {
+                let _ = session.text(message).await;
+            }
+            Err(err) => {
+                let _ = session.text(err.to_string()).await;
+            }
+        }
+
+        let _ = session.close(None).await;
+    });
+
+    Ok(response)
+}
@@ -0,0 +1,199 @@
+use actix_web::{Error, HttpRequest, HttpResponse, web};
+use actix_ws::Message;
+use serde::{Deserialize, Serialize};
+use stego_wave::configuration::StegoWaveLib;
+use stego_wave::formats::get_stego_by_str;
+use stego_wave::object::StegoSamples;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+
+/// Control frame opening a `/api/ws/process` session: a JSON text frame describing
+/// the job, followed by one or more binary frames carrying the audio file.
+///
+/// Unlike [`super::websocket::stego_ws`]'s `{command, ...}` shape, this tags the
+/// operation separately from `options` so `id` (echoed back on every
+/// [`ProgressFrame`]) and `name` don't have to live inside a `serde(tag = ...)`
+/// variant.
+#[derive(Deserialize)]
+struct ProcessControl {
+    /// Client-assigned label for this job. Not interpreted by the server, just
+    /// accepted so a UI juggling several concurrent uploads can tell them apart.
+    #[allow(dead_code)]
+    name: String,
+    #[serde(rename = "type")]
+    kind: ProcessKind,
+    id: String,
+    options: ProcessOptions,
+}
+
+#[derive(Deserialize)]
+enum ProcessKind {
+    Hide,
+    Extract,
+    Clear,
+}
+
+#[derive(Deserialize)]
+struct ProcessOptions {
+    format: String,
+    password: String,
+    lsb_deep: u8,
+    /// Only read for [`ProcessKind::Hide`]; ignored otherwise.
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    compress: bool,
+    /// Only read for [`ProcessKind::Hide`]; ignored otherwise.
+    #[serde(default)]
+    encrypt: bool,
+}
+
+/// A progress tick sent back as a JSON text frame while a job is running, reporting
+/// how many of the carrier's samples the embed/extract/clear pass has consumed.
+#[derive(Serialize)]
+struct ProgressFrame<'a> {
+    id: &'a str,
+    processed_samples: usize,
+    total_samples: usize,
+}
+
+enum ProcessOutcome {
+    Audio(StegoSamples),
+    Message(String),
+}
+
+/// Accepts a [`ProcessControl`] frame followed by the audio file's binary frames,
+/// then runs it through the same `get_stego_by_str` + read/process/write pipeline
+/// [`super::stego_wave`] uses, but forwards incremental [`ProgressFrame`]s over the
+/// same session while it works instead of replying only once at the end. The final
+/// reply is a binary frame for `Hide`/`Clear`, or a text frame for `Extract`, same
+/// as [`super::websocket::stego_ws`].
+pub async fn process_ws(
+    req: HttpRequest,
+    stream: web::Payload,
+    settings: web::Data<StegoWaveLib>,
+) -> Result<HttpResponse, Error> {
+    let (response, mut session, mut msg_stream) = actix_ws::handle(&req, stream)?;
+
+    actix_web::rt::spawn(async move {
+        let mut control: Option<ProcessControl> = None;
+        let mut file = Vec::new();
+
+        while let Some(Ok(msg)) = msg_stream.next().await {
+            match msg {
+                Message::Text(text) if control.is_none() => match serde_json::from_str(&text) {
+                    Ok(frame) => control = Some(frame),
+                    Err(err) => {
+                        let _ = session.text(format!("Invalid control frame: {err}")).await;
+                        let _ = session.close(None).await;
+                        return;
+                    }
+                },
+                Message::Binary(bytes) => file.extend_from_slice(&bytes),
+                Message::Close(reason) => {
+                    let _ = session.close(reason).await;
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        let Some(control) = control else {
+            let _ = session.text("Missing control frame").await;
+            let _ = session.close(None).await;
+            return;
+        };
+
+        let ProcessControl { kind, id, options, .. } = control;
+
+        let stego = match get_stego_by_str(
+            &options.format,
+            options.lsb_deep,
+            options.compress,
+            (*settings.into_inner()).clone(),
+        ) {
+            Ok(stego) => stego,
+            Err(err) => {
+                let _ = session.text(err.to_string()).await;
+                let _ = session.close(None).await;
+                return;
+            }
+        };
+
+        let (mut samples, spec) = match stego.read_samples_from_byte(file) {
+            Ok(data) => data,
+            Err(err) => {
+                let _ = session.text(err.to_string()).await;
+                let _ = session.close(None).await;
+                return;
+            }
+        };
+
+        // Progress ticks raised from the (synchronous) embedding pass below are
+        // forwarded to the client by a sibling task so they go out as soon as
+        // they're raised instead of being buffered until the pass finishes.
+        let (tx, mut rx) = mpsc::unbounded_channel::<(usize, usize)>();
+        let mut forward_session = session.clone();
+        let forward_task = actix_web::rt::spawn(async move {
+            while let Some((processed, total)) = rx.recv().await {
+                let frame = ProgressFrame { id: &id, processed_samples: processed, total_samples: total };
+                if let Ok(json) = serde_json::to_string(&frame) {
+                    if forward_session.text(json).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        let mut on_progress = move |processed: usize, total: usize| {
+            let _ = tx.send((processed, total));
+        };
+
+        let result = match kind {
+            ProcessKind::Hide => stego
+                .hide_message_binary_with_progress(
+                    &mut samples,
+                    &options.message,
+                    &options.password,
+                    options.encrypt,
+                    &mut on_progress,
+                )
+                .map(|()| ProcessOutcome::Audio(samples)),
+            ProcessKind::Extract => {
+                on_progress(0, samples.len());
+                let result = stego.extract_message_binary(&samples, &options.password);
+                on_progress(samples.len(), samples.len());
+                result.map(ProcessOutcome::Message)
+            }
+            ProcessKind::Clear => {
+                on_progress(0, samples.len());
+                let result = stego.clear_secret_message_binary(&mut samples, &options.password);
+                on_progress(samples.len(), samples.len());
+                result.map(|()| ProcessOutcome::Audio(samples))
+            }
+        };
+        drop(on_progress);
+        let _ = forward_task.await;
+
+        match result {
+            Ok(ProcessOutcome::Audio(samples)) => match stego.write_samples_to_byte(spec, &samples) {
+                Ok(bytes) => {
+                    let _ = session.binary(bytes).await;
+                }
+                Err(err) => {
+                    let _ = session.text(err.to_string()).await;
+                }
+            },
+            Ok(ProcessOutcome::Message(message)) => {
+                let _ = session.text(message).await;
+            }
+            Err(err) => {
+                let _ = session.text(err.to_string()).await;
+            }
+        }
+
+        let _ = session.close(None).await;
+    });
+
+    Ok(response)
+}
@@ -1,17 +1,34 @@
 use serde::Deserialize;
 use std::mem;
+use stego_wave::auth::AuthConfig;
 use stego_wave::configuration::StegoWaveLib;
+use stego_wave::share::ShareConfig;
+use stego_wave::tls::TlsConfig;
+
+fn default_max_upload_bytes() -> u64 {
+    500 * 1024 * 1024
+}
 
 #[derive(Deserialize)]
 pub struct RestConfig {
     pub host: String,
     pub port: u32,
+    /// Hard cap on a `file` multipart field's size; requests over this are rejected
+    /// with `413 Payload Too Large` before the whole upload is read. See
+    /// [`crate::services::stego_wave::parse_multipart_payload`].
+    #[serde(default = "default_max_upload_bytes")]
+    pub max_upload_bytes: u64,
 }
 
 #[derive(Deserialize)]
 pub struct Settings {
     pub rest: RestConfig,
     pub stego_wave_lib: StegoWaveLib,
+    pub auth: AuthConfig,
+    #[serde(default)]
+    pub tls: TlsConfig,
+    #[serde(default)]
+    pub share: ShareConfig,
 }
 
 impl Settings {
@@ -30,4 +47,16 @@ impl Settings {
     pub fn get_stego_wave_lib_settings(&mut self) -> StegoWaveLib {
         mem::take(&mut self.stego_wave_lib)
     }
+
+    pub fn get_auth_settings(&mut self) -> AuthConfig {
+        mem::take(&mut self.auth)
+    }
+
+    pub fn get_tls_settings(&mut self) -> TlsConfig {
+        mem::take(&mut self.tls)
+    }
+
+    pub fn get_share_settings(&mut self) -> ShareConfig {
+        mem::take(&mut self.share)
+    }
 }
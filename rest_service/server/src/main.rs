@@ -1,5 +1,8 @@
 use rest_server::{configuration, startup::run_server, tracing_config};
 use std::net::TcpListener;
+use std::sync::Arc;
+use stego_wave::auth::TokenAuthority;
+use stego_wave::share::{ShareBackend, ShareStore};
 
 const CONFIG_FILE: &str = "sw_config.toml";
 
@@ -9,9 +12,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let mut settings = configuration::Settings::new(CONFIG_FILE)?;
     let stego_wave_setting = settings.get_stego_wave_lib_settings();
+    let auth = Arc::new(TokenAuthority::new(&settings.get_auth_settings())?);
+    let tls = settings.get_tls_settings().load()?;
+    let share_store: Arc<dyn ShareBackend> = Arc::new(ShareStore::new(&settings.get_share_settings()));
+    let max_upload_bytes = settings.rest.max_upload_bytes;
     let listener = TcpListener::bind(settings.address())?;
 
-    run_server(listener, stego_wave_setting)?.await?;
+    run_server(
+        listener,
+        stego_wave_setting,
+        auth,
+        tls,
+        share_store,
+        max_upload_bytes,
+    )?
+    .await?;
 
     Ok(())
 }
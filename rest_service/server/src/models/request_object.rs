@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 #[allow(unused)]
@@ -14,6 +14,12 @@ pub struct HideRequest {
     pub format: String,
     #[schema(example = "1", minimum = 1)]
     pub lsb_deep: u8,
+    #[schema(example = "false")]
+    pub as_share: bool,
+    #[schema(example = "false")]
+    pub compress: bool,
+    #[schema(example = "false")]
+    pub encrypt: bool,
 }
 
 #[allow(unused)]
@@ -40,4 +46,33 @@ pub struct ClearRequest {
     pub format: String,
     #[schema(example = "1", minimum = 1)]
     pub lsb_deep: u8,
+    #[schema(example = "false")]
+    pub as_share: bool,
+}
+
+#[allow(unused)]
+#[derive(Deserialize, ToSchema)]
+pub struct CapacityRequest {
+    #[schema(value_type = String, format = "binary")]
+    pub file: Vec<u8>,
+    #[schema(example = "wav16")]
+    pub format: String,
+    #[schema(example = "1", minimum = 1)]
+    pub lsb_deep: u8,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CapacityResponse {
+    #[schema(example = "1234")]
+    pub capacity_bytes: usize,
+    #[schema(example = "58")]
+    pub overhead_bytes: usize,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ShareResponse {
+    #[schema(example = "3f9c2b1a...")]
+    pub token: String,
+    #[schema(example = "/share/3f9c2b1a...")]
+    pub url: String,
 }
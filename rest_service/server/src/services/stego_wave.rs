@@ -1,10 +1,38 @@
-use crate::models::request_object::{ClearRequest, ExtractRequest, HideRequest};
+use crate::models::request_object::{CapacityRequest, ClearRequest, ExtractRequest, HideRequest};
 use actix_multipart::{Field, Multipart};
 use futures::{StreamExt, TryStreamExt};
-use std::error::Error;
+use rand::RngCore;
+use rand::rngs::OsRng;
+use std::fmt;
 use std::mem;
+use std::path::PathBuf;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
 use tracing::{debug, warn};
 
+/// Once a `file` field's buffered bytes pass this size, further chunks are written to
+/// a temporary file instead of growing one `Vec<u8>`, bounding how large a single
+/// in-memory allocation gets while the upload is still arriving over the wire.
+const SPILL_THRESHOLD_BYTES: usize = 8 * 1024 * 1024;
+
+#[derive(Debug)]
+pub enum UploadError {
+    /// The `file` field passed `max_upload_bytes` before the stream ended.
+    TooLarge,
+    Multipart(String),
+}
+
+impl fmt::Display for UploadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UploadError::TooLarge => {
+                write!(f, "Uploaded file exceeds the configured size limit")
+            }
+            UploadError::Multipart(err) => write!(f, "{err}"),
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct MultipartyPayload {
     pub file_bytes: Option<Vec<u8>>,
@@ -12,6 +40,9 @@ pub struct MultipartyPayload {
     pub password: Option<String>,
     pub format: Option<String>,
     pub lsb_deep: Option<u8>,
+    pub as_share: bool,
+    pub compress: bool,
+    pub encrypt: bool,
 }
 
 impl TryFrom<MultipartyPayload> for HideRequest {
@@ -24,6 +55,9 @@ impl TryFrom<MultipartyPayload> for HideRequest {
             password: value.get_password()?,
             format: value.get_format()?,
             lsb_deep: value.get_lsb_deep()?,
+            as_share: value.as_share,
+            compress: value.compress,
+            encrypt: value.encrypt,
         })
     }
 }
@@ -48,6 +82,18 @@ impl TryFrom<MultipartyPayload> for ClearRequest {
             password: value.get_password()?,
             format: value.get_format()?,
             lsb_deep: value.get_lsb_deep()?,
+            as_share: value.as_share,
+        })
+    }
+}
+
+impl TryFrom<MultipartyPayload> for CapacityRequest {
+    type Error = String;
+    fn try_from(mut value: MultipartyPayload) -> Result<Self, Self::Error> {
+        Ok(CapacityRequest {
+            file: value.get_file_bytes()?,
+            format: value.get_format()?,
+            lsb_deep: value.get_lsb_deep()?,
         })
     }
 }
@@ -94,12 +140,20 @@ impl MultipartyPayload {
     }
 }
 
-pub async fn parse_multipart_payload(mut payload: Multipart) -> Result<MultipartyPayload, String> {
+/// Parses a `multipart/form-data` body into a [`MultipartyPayload`], rejecting the
+/// `file` field once it passes `max_upload_bytes` instead of buffering it in full.
+pub async fn parse_multipart_payload(
+    mut payload: Multipart,
+    max_upload_bytes: u64,
+) -> Result<MultipartyPayload, UploadError> {
     let mut file_bytes = None;
     let mut message = None;
     let mut password = None;
     let mut format = None;
     let mut lsb_deep = None;
+    let mut as_share = false;
+    let mut compress = false;
+    let mut encrypt = false;
 
     while let Ok(Some(field)) = payload.try_next().await {
         let name = field
@@ -109,40 +163,64 @@ pub async fn parse_multipart_payload(mut payload: Multipart) -> Result<Multipart
 
         match name {
             "file" => {
-                let data = get_byte_from_field(field).await.map_err(|err| {
-                    warn!("Failed to get |file| :: {err}");
-                    "Failed to get |file|"
-                })?;
+                let data = match get_byte_from_field(field, max_upload_bytes).await {
+                    Ok(data) => data,
+                    Err(err) => {
+                        warn!("Failed to get |file| :: {err}");
+                        return Err(err);
+                    }
+                };
                 file_bytes = Some(data);
             }
             "message" => {
                 let text = get_text_from_field(field).await.map_err(|err| {
                     warn!("Failed to get |message| :: {err}");
-                    format!("Failed to get |message| :: {err}")
+                    UploadError::Multipart(format!("Failed to get |message| :: {err}"))
                 })?;
                 message = Some(text);
             }
             "password" => {
                 let text = get_text_from_field(field).await.map_err(|err| {
                     warn!("Failed to get |password| :: {err}");
-                    format!("Failed to get |password| :: {err}")
+                    UploadError::Multipart(format!("Failed to get |password| :: {err}"))
                 })?;
                 password = Some(text);
             }
             "format" => {
                 let text = get_text_from_field(field).await.map_err(|err| {
                     warn!("Failed to get |format| :: {err}");
-                    format!("Failed to get |format| :: {err}")
+                    UploadError::Multipart(format!("Failed to get |format| :: {err}"))
                 })?;
                 format = Some(text);
             }
             "lsb_deep" => {
                 let text = get_text_from_field(field).await.map_err(|err| {
                     warn!("Failed to get |lsb_deep| :: {err}");
-                    format!("Failed to get |lsb_deep| :: {err}")
+                    UploadError::Multipart(format!("Failed to get |lsb_deep| :: {err}"))
                 })?;
                 lsb_deep = Some(text.parse::<u8>().unwrap_or(1));
             }
+            "as_share" => {
+                let text = get_text_from_field(field).await.map_err(|err| {
+                    warn!("Failed to get |as_share| :: {err}");
+                    UploadError::Multipart(format!("Failed to get |as_share| :: {err}"))
+                })?;
+                as_share = text.parse::<bool>().unwrap_or(false);
+            }
+            "compress" => {
+                let text = get_text_from_field(field).await.map_err(|err| {
+                    warn!("Failed to get |compress| :: {err}");
+                    UploadError::Multipart(format!("Failed to get |compress| :: {err}"))
+                })?;
+                compress = text.parse::<bool>().unwrap_or(false);
+            }
+            "encrypt" => {
+                let text = get_text_from_field(field).await.map_err(|err| {
+                    warn!("Failed to get |encrypt| :: {err}");
+                    UploadError::Multipart(format!("Failed to get |encrypt| :: {err}"))
+                })?;
+                encrypt = text.parse::<bool>().unwrap_or(false);
+            }
             _ => {}
         }
     }
@@ -157,19 +235,91 @@ pub async fn parse_multipart_payload(mut payload: Multipart) -> Result<Multipart
         password,
         format,
         lsb_deep,
+        as_share,
+        compress,
+        encrypt,
     })
 }
 
-async fn get_byte_from_field(mut field: Field) -> Result<Vec<u8>, Box<dyn Error>> {
-    let mut bytes = Vec::new();
+/// Reads the `file` field in full, aborting once its total size passes
+/// `max_upload_bytes` and spilling to a temporary file past
+/// [`SPILL_THRESHOLD_BYTES`] so no single chunked `extend_from_slice` pass has to
+/// grow a multi-hundred-megabyte `Vec` in place.
+///
+/// This only bounds memory *while the upload is in flight*. The temporary file is
+/// read back into one `Vec` before returning, so peak memory for the subsequent
+/// hide/extract/clear pass still scales with the file's size — it is bounded by
+/// `max_upload_bytes`, not constant. True streaming would mean
+/// [`stego_wave::object::AudioSteganography`] reading samples directly off a
+/// reader/path instead of an owned buffer, which is a larger change than this
+/// function can make on its own and remains outstanding.
+async fn get_byte_from_field(mut field: Field, max_upload_bytes: u64) -> Result<Vec<u8>, UploadError> {
+    let mut total_len: u64 = 0;
+    let mut buffer = Vec::new();
+    let mut spill: Option<(fs::File, PathBuf)> = None;
+
     while let Some(chunk) = field.next().await {
-        let data = chunk?;
-        bytes.extend_from_slice(&data);
+        let data = chunk.map_err(|err| UploadError::Multipart(err.to_string()))?;
+        total_len += data.len() as u64;
+
+        if total_len > max_upload_bytes {
+            if let Some((_, path)) = spill.take() {
+                let _ = fs::remove_file(&path).await;
+            }
+            return Err(UploadError::TooLarge);
+        }
+
+        match &mut spill {
+            Some((file, _)) => {
+                file.write_all(&data)
+                    .await
+                    .map_err(|err| UploadError::Multipart(err.to_string()))?;
+            }
+            None => {
+                buffer.extend_from_slice(&data);
+                if buffer.len() > SPILL_THRESHOLD_BYTES {
+                    let (mut file, path) = create_spill_file()
+                        .await
+                        .map_err(|err| UploadError::Multipart(err.to_string()))?;
+                    file.write_all(&buffer)
+                        .await
+                        .map_err(|err| UploadError::Multipart(err.to_string()))?;
+                    buffer.clear();
+                    spill = Some((file, path));
+                }
+            }
+        }
     }
-    Ok(bytes)
+
+    match spill {
+        Some((mut file, path)) => {
+            file.flush()
+                .await
+                .map_err(|err| UploadError::Multipart(err.to_string()))?;
+            let bytes = fs::read(&path)
+                .await
+                .map_err(|err| UploadError::Multipart(err.to_string()))?;
+            let _ = fs::remove_file(&path).await;
+            Ok(bytes)
+        }
+        None => Ok(buffer),
+    }
+}
+
+/// Creates a uniquely-named temporary file under the system temp dir for a spilled
+/// `file` field; the caller removes it once the upload finishes or is rejected.
+async fn create_spill_file() -> std::io::Result<(fs::File, PathBuf)> {
+    let mut name_bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut name_bytes);
+    let name: String = name_bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+
+    let path = std::env::temp_dir().join(format!("stego_upload_{name}.tmp"));
+    let file = fs::File::create(&path).await?;
+
+    Ok((file, path))
 }
 
-async fn get_text_from_field(mut field: Field) -> Result<String, Box<dyn Error>> {
+async fn get_text_from_field(mut field: Field) -> Result<String, Box<dyn std::error::Error>> {
     let mut bytes = Vec::new();
     while let Some(chunk) = field.next().await {
         let data = chunk?;
@@ -1,29 +1,137 @@
-use actix_web::dev::Server;
-use actix_web::{App, HttpServer, web};
+use actix_web::body::MessageBody;
+use actix_web::dev::{Server, ServiceRequest, ServiceResponse};
+use actix_web::http::header;
+use actix_web::middleware::{Next, from_fn};
+use actix_web::{App, Error, HttpResponse, HttpServer, web};
+use metrics_exporter_prometheus::PrometheusHandle;
 use std::net::TcpListener;
+use std::sync::Arc;
+use std::time::Duration;
+use stego_wave::auth::TokenAuthority;
 use stego_wave::configuration::Settings;
+use stego_wave::metrics::install_recorder;
+use stego_wave::share::ShareBackend;
+use stego_wave::tls::TlsMaterial;
 use tracing_actix_web::TracingLogger;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
+/// How often the share store is swept for expired entries.
+const EXPIRY_SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
 pub fn run_server(
     listener: TcpListener,
     stego_wave_lib: Settings,
+    auth: Arc<TokenAuthority>,
+    tls: Option<TlsMaterial>,
+    share_store: Arc<dyn ShareBackend>,
+    max_upload_bytes: u64,
 ) -> Result<Server, std::io::Error> {
     let settings = web::Data::new(stego_wave_lib);
+    let auth = web::Data::new(auth);
+    let metrics_handle = web::Data::new(install_recorder());
+    let share_store = web::Data::new(share_store);
+    let max_upload_bytes = web::Data::new(max_upload_bytes);
+
+    spawn_share_sweeper(share_store.as_ref().clone());
 
-    let server = HttpServer::new(move || {
+    let http_server = HttpServer::new(move || {
         App::new()
             .wrap(TracingLogger::default())
+            .wrap(from_fn(require_auth))
             .configure(super::api::stego_wave::routers)
+            .route("/metrics", web::get().to(metrics))
+            .route("/ws/stego", web::get().to(crate::api::websocket::stego_ws))
+            .route(
+                "/api/ws/process",
+                web::get().to(crate::api::ws_process::process_ws),
+            )
+            .route(
+                "/share/{token}",
+                web::get().to(crate::api::share::fetch_share),
+            )
             .service(
                 SwaggerUi::new("/swagger-ui/{_:.*}")
                     .url("/api/openapi.json", crate::api::api_docs::ApiDoc::openapi()),
             )
             .app_data(settings.clone())
-    })
-    .listen(listener)?
-    .run();
+            .app_data(auth.clone())
+            .app_data(metrics_handle.clone())
+            .app_data(share_store.clone())
+            .app_data(max_upload_bytes.clone())
+    });
+
+    let server = match tls {
+        Some(material) => http_server.listen_rustls_0_23(listener, rustls_config(material))?,
+        None => http_server.listen(listener)?,
+    };
+
+    Ok(server.run())
+}
+
+/// Builds the rustls server config actix-web binds the listener with.
+///
+/// Audio payloads carry hidden messages, so TLS is the default; callers only reach
+/// this path once [`stego_wave::tls::TlsConfig::load`] has confirmed a cert/key pair
+/// was configured (or panicked the process on a malformed one — there is no sensible
+/// way to serve traffic on a listener that failed to come up correctly).
+fn rustls_config(material: TlsMaterial) -> rustls::ServerConfig {
+    let cert_chain = rustls_pemfile::certs(&mut material.cert_pem.as_slice())
+        .collect::<Result<Vec<_>, _>>()
+        .expect("invalid TLS certificate");
+    let key = rustls_pemfile::private_key(&mut material.key_pem.as_slice())
+        .expect("invalid TLS private key")
+        .expect("TLS key file contained no private key");
+
+    rustls::ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(cert_chain, key)
+        .expect("TLS certificate does not match private key")
+}
+
+/// Renders the process's counters and histograms in the Prometheus text exposition format.
+async fn metrics(handle: web::Data<PrometheusHandle>) -> impl actix_web::Responder {
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(handle.render())
+}
+
+/// Rejects requests that do not carry an `Authorization: Bearer <token>` header
+/// naming a known, unexpired token. `/metrics` is exempt so Prometheus can scrape
+/// it without provisioning it a token, and `/share/{token}` is exempt since a share
+/// link's token is itself the credential and is meant to be handed to a plain browser.
+async fn require_auth(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let authorized = req.path() == "/metrics"
+        || req.path().starts_with("/share/")
+        || req
+            .app_data::<web::Data<Arc<TokenAuthority>>>()
+            .zip(bearer_token(&req))
+            .is_some_and(|(auth, token)| auth.authorize(&token));
+
+    if !authorized {
+        let response = HttpResponse::Unauthorized().finish();
+        return Ok(req.into_response(response).map_into_boxed_body());
+    }
+
+    next.call(req).await.map(ServiceResponse::map_into_boxed_body)
+}
+
+fn bearer_token(req: &ServiceRequest) -> Option<String> {
+    req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::to_string)
+}
 
-    Ok(server)
+fn spawn_share_sweeper(share_store: Arc<dyn ShareBackend>) {
+    actix_web::rt::spawn(async move {
+        loop {
+            actix_web::rt::time::sleep(EXPIRY_SWEEP_INTERVAL).await;
+            share_store.sweep_expired();
+        }
+    });
 }
@@ -0,0 +1,12 @@
+use socket_server::configuration;
+use socket_server::startup::run_server;
+
+const CONFIG_FILE: &str = "sw_config.toml";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let settings = configuration::Settings::new(CONFIG_FILE)?;
+
+    run_server(settings).await?;
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use std::io;
+
+use stego_wave::command::{CommandRequest, StegoOutcome, execute};
+use stego_wave::configuration::StegoWaveLib;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::UnixStream;
+
+const TAG_AUDIO: u8 = 0;
+const TAG_MESSAGE: u8 = 1;
+const TAG_ERROR: u8 = 2;
+
+/// Handles one client connection: a length-prefixed JSON [`CommandRequest`] frame
+/// followed by a length-prefixed raw file frame, run through the same
+/// [`stego_wave::command::execute`] pipeline the gRPC and WebSocket gateways use,
+/// and answered with a tagged, length-prefixed response frame.
+pub async fn handle_connection(mut stream: UnixStream, settings: StegoWaveLib) -> io::Result<()> {
+    let control = read_frame(&mut stream).await?;
+    let file = read_frame(&mut stream).await?;
+
+    let control: CommandRequest = match serde_json::from_slice(&control) {
+        Ok(control) => control,
+        Err(err) => {
+            return write_frame(
+                &mut stream,
+                TAG_ERROR,
+                format!("Invalid control frame: {err}").into_bytes(),
+            )
+            .await;
+        }
+    };
+
+    let command = control.into_command(file);
+    match execute(command, settings) {
+        Ok(StegoOutcome::Audio(bytes)) => write_frame(&mut stream, TAG_AUDIO, bytes).await,
+        Ok(StegoOutcome::Message(message)) => {
+            write_frame(&mut stream, TAG_MESSAGE, message.into_bytes()).await
+        }
+        Err(err) => write_frame(&mut stream, TAG_ERROR, err.to_string().into_bytes()).await,
+    }
+}
+
+async fn read_frame(stream: &mut UnixStream) -> io::Result<Vec<u8>> {
+    let len = stream.read_u32().await? as usize;
+    let mut buf = vec![0u8; len];
+    stream.read_exact(&mut buf).await?;
+    Ok(buf)
+}
+
+async fn write_frame(stream: &mut UnixStream, tag: u8, payload: Vec<u8>) -> io::Result<()> {
+    stream.write_u8(tag).await?;
+    stream.write_u32(payload.len() as u32).await?;
+    stream.write_all(&payload).await?;
+    stream.flush().await
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use tokio::net::UnixListener;
+
+use crate::configuration::Settings;
+use crate::protocol::handle_connection;
+
+/// Binds the Unix-domain-socket gateway and serves connections until the process exits
+/// or a listener error occurs.
+///
+/// Unlike the gRPC/REST gateways, clients here are not asked for a bearer token: the
+/// socket is reachable only by same-host tooling, and the filesystem permissions on
+/// `settings.socket.path` are the access control.
+pub async fn run_server(settings: Settings) -> std::io::Result<()> {
+    let _ = std::fs::remove_file(&settings.socket.path);
+    let listener = UnixListener::bind(&settings.socket.path)?;
+    let stego_wave_lib = Arc::new(settings.stego_wave_lib);
+
+    loop {
+        let (stream, _addr) = listener.accept().await?;
+        let stego_wave_lib = stego_wave_lib.clone();
+
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(stream, (*stego_wave_lib).clone()).await {
+                eprintln!("socket connection error: {err}");
+            }
+        });
+    }
+}
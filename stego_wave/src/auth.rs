@@ -0,0 +1,104 @@
+use crate::error::StegoError;
+use rand::RngCore;
+use rand::rngs::OsRng;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+const TOKEN_LEN: usize = 32;
+
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct AuthConfig {
+    pub tokens_file: String,
+}
+
+/// Issues and validates long-lived bearer tokens for the gRPC and REST servers.
+///
+/// Tokens are read from and appended to `tokens_file`; there is no scoping or
+/// expiry, every valid token grants full access to every endpoint.
+pub struct TokenAuthority {
+    long_lived: Mutex<HashSet<String>>,
+    tokens_file: PathBuf,
+}
+
+impl TokenAuthority {
+    pub fn new(config: &AuthConfig) -> Result<Self, StegoError> {
+        let tokens_file = PathBuf::from(&config.tokens_file);
+        let long_lived = read_tokens_file(&tokens_file)?;
+
+        Ok(Self {
+            long_lived: Mutex::new(long_lived),
+            tokens_file,
+        })
+    }
+
+    /// Mints a new long-lived token and persists it to the tokens file.
+    pub fn issue_long_lived_token(&self) -> Result<String, StegoError> {
+        let token = generate_token();
+        let mut long_lived = self.long_lived.lock().unwrap();
+        long_lived.insert(token.clone());
+
+        let contents = long_lived.iter().cloned().collect::<Vec<_>>().join("\n");
+        fs::write(&self.tokens_file, contents)
+            .map_err(|err| StegoError::Other(format!("Failed to persist tokens file: {err}")))?;
+
+        Ok(token)
+    }
+
+    /// Returns `true` if `token` is a known long-lived token.
+    pub fn authorize(&self, token: &str) -> bool {
+        self.long_lived.lock().unwrap().contains(token)
+    }
+}
+
+fn read_tokens_file(path: &PathBuf) -> Result<HashSet<String>, StegoError> {
+    if !path.exists() {
+        return Ok(HashSet::new());
+    }
+
+    let contents = fs::read_to_string(path)
+        .map_err(|err| StegoError::Other(format!("Failed to read tokens file: {err}")))?;
+
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; TOKEN_LEN];
+    OsRng.fill_bytes(&mut bytes);
+
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn authority() -> TokenAuthority {
+        TokenAuthority::new(&AuthConfig {
+            tokens_file: "/tmp/stego_wave_auth_tests_nonexistent_tokens".to_string(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn issued_token_is_authorized() {
+        let authority = authority();
+        let token = authority.issue_long_lived_token().unwrap();
+
+        assert!(authority.authorize(&token));
+    }
+
+    #[test]
+    fn unknown_token_is_not_authorized() {
+        let authority = authority();
+
+        assert!(!authority.authorize("not-a-real-token"));
+    }
+}
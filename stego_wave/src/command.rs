@@ -0,0 +1,170 @@
+use crate::configuration::StegoWaveLib;
+use crate::error::GetStegoError;
+use crate::formats::get_stego_by_str;
+use serde::{Deserialize, Serialize};
+
+/// A transport-independent description of one hide/extract/clear request.
+///
+/// A gateway (gRPC, WebSocket, Unix socket, ...) builds one of these from whatever
+/// wire format it speaks, then hands it to [`execute`] so every frontend runs the
+/// same get_stego_by_str + read/process/write pipeline instead of its own copy.
+pub enum StegoCommand {
+    Hide {
+        file: Vec<u8>,
+        message: String,
+        password: String,
+        format: String,
+        lsb_deep: u8,
+        compress: bool,
+        encrypt: bool,
+    },
+    Extract {
+        file: Vec<u8>,
+        password: String,
+        format: String,
+        lsb_deep: u8,
+    },
+    Clear {
+        file: Vec<u8>,
+        password: String,
+        format: String,
+        lsb_deep: u8,
+    },
+}
+
+/// The JSON-deserializable shape of a [`StegoCommand`] with its `file` split off,
+/// since the file itself arrives as a separate binary frame/payload on every
+/// gateway that speaks JSON for its control message (the WebSocket and
+/// Unix-socket gateways). `Serialize` lets a client build one of these and send
+/// it as that control frame instead of hand-rolling the same JSON shape.
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum CommandRequest {
+    Hide {
+        message: String,
+        password: String,
+        format: String,
+        lsb_deep: u8,
+        #[serde(default)]
+        compress: bool,
+        #[serde(default)]
+        encrypt: bool,
+    },
+    Extract {
+        password: String,
+        format: String,
+        lsb_deep: u8,
+    },
+    Clear {
+        password: String,
+        format: String,
+        lsb_deep: u8,
+    },
+}
+
+impl CommandRequest {
+    /// Reunites this control message with the `file` bytes it was sent alongside.
+    pub fn into_command(self, file: Vec<u8>) -> StegoCommand {
+        match self {
+            CommandRequest::Hide {
+                message,
+                password,
+                format,
+                lsb_deep,
+                compress,
+                encrypt,
+            } => StegoCommand::Hide {
+                file,
+                message,
+                password,
+                format,
+                lsb_deep,
+                compress,
+                encrypt,
+            },
+            CommandRequest::Extract {
+                password,
+                format,
+                lsb_deep,
+            } => StegoCommand::Extract {
+                file,
+                password,
+                format,
+                lsb_deep,
+            },
+            CommandRequest::Clear {
+                password,
+                format,
+                lsb_deep,
+            } => StegoCommand::Clear {
+                file,
+                password,
+                format,
+                lsb_deep,
+            },
+        }
+    }
+}
+
+/// The result of running a [`StegoCommand`]: the processed audio file for
+/// `Hide`/`Clear`, or the extracted message for `Extract`.
+pub enum StegoOutcome {
+    Audio(Vec<u8>),
+    Message(String),
+}
+
+/// Error produced by [`execute`]: either the requested format/`lsb_deep` combination
+/// doesn't exist, or the steganography pipeline itself failed.
+#[derive(Debug, thiserror::Error)]
+pub enum CommandError {
+    #[error("{0}")]
+    UnknownFormat(#[from] GetStegoError),
+
+    #[error("{0}")]
+    Stego(#[from] crate::error::StegoError),
+}
+
+/// Runs `command` against `settings`, owning the full get_stego_by_str +
+/// read/process/write pipeline so every gateway shares one audited code path.
+pub fn execute(command: StegoCommand, settings: StegoWaveLib) -> Result<StegoOutcome, CommandError> {
+    match command {
+        StegoCommand::Hide {
+            file,
+            message,
+            password,
+            format,
+            lsb_deep,
+            compress,
+            encrypt,
+        } => {
+            let stego = get_stego_by_str(&format, lsb_deep, compress, settings)?;
+            let (mut samples, spec) = stego.read_samples_from_byte(file)?;
+            stego.hide_message_binary(&mut samples, &message, &password, encrypt)?;
+            let out = stego.write_samples_to_byte(spec, &samples)?;
+            Ok(StegoOutcome::Audio(out))
+        }
+        StegoCommand::Extract {
+            file,
+            password,
+            format,
+            lsb_deep,
+        } => {
+            let stego = get_stego_by_str(&format, lsb_deep, false, settings)?;
+            let (samples, _spec) = stego.read_samples_from_byte(file)?;
+            let message = stego.extract_message_binary(&samples, &password)?;
+            Ok(StegoOutcome::Message(message))
+        }
+        StegoCommand::Clear {
+            file,
+            password,
+            format,
+            lsb_deep,
+        } => {
+            let stego = get_stego_by_str(&format, lsb_deep, false, settings)?;
+            let (mut samples, spec) = stego.read_samples_from_byte(file)?;
+            stego.clear_secret_message_binary(&mut samples, &password)?;
+            let out = stego.write_samples_to_byte(spec, &samples)?;
+            Ok(StegoOutcome::Audio(out))
+        }
+    }
+}
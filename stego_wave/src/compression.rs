@@ -0,0 +1,141 @@
+//! Optional Snappy compression layer for the hidden message, applied before
+//! encryption so it's the smaller compressed form that actually gets LSB-embedded.
+//!
+//! [`compress`] prefixes its output with a one-byte codec id and a varint of the
+//! uncompressed length, falling back to storing the message unmodified whenever
+//! compression doesn't actually shrink it (short or already-dense payloads would
+//! otherwise pay the frame header for nothing). [`decompress`] reverses it.
+
+use crate::error::StegoError;
+use crate::object::ResultStego;
+
+/// Frame codec id: the body is the message as-is, with no compression applied.
+const CODEC_STORED: u8 = 0;
+/// Frame codec id: the body is the message Snappy-compressed.
+const CODEC_SNAPPY: u8 = 1;
+
+const MAX_VARINT_BYTES: usize = 5;
+
+/// Worst-case byte length of a frame's codec id + length prefix, before its body.
+pub const MAX_FRAME_OVERHEAD: usize = 1 + MAX_VARINT_BYTES;
+
+fn encode_varint(mut value: u64) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(MAX_VARINT_BYTES);
+
+    loop {
+        let mut byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        bytes.push(byte);
+
+        if value == 0 {
+            break;
+        }
+    }
+
+    bytes
+}
+
+fn decode_varint(byte_iter: &mut impl Iterator<Item = u8>) -> ResultStego<u64> {
+    let mut value: u64 = 0;
+
+    for shift in (0..MAX_VARINT_BYTES).map(|i| i * 7) {
+        let byte = byte_iter.next().ok_or(StegoError::FailedToReceiveMessage)?;
+        value |= ((byte & 0x7F) as u64) << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(StegoError::FailedToReceiveMessage)
+}
+
+fn frame(codec: u8, uncompressed_len: u64, body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(1 + MAX_VARINT_BYTES + body.len());
+    out.push(codec);
+    out.extend(encode_varint(uncompressed_len));
+    out.extend_from_slice(body);
+    out
+}
+
+/// Compresses `data`, returning a self-describing frame extraction can restore
+/// without being told whether compression was actually used.
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    let compressed = snap::raw::Encoder::new().compress_vec(data).ok();
+
+    match compressed {
+        Some(compressed) if compressed.len() < data.len() => {
+            frame(CODEC_SNAPPY, data.len() as u64, &compressed)
+        }
+        _ => frame(CODEC_STORED, data.len() as u64, data),
+    }
+}
+
+/// Wraps `data` in the same self-describing frame as [`compress`], without
+/// attempting compression. Used when compression is disabled, so [`decompress`]
+/// never needs to be told whether the embedding side had it turned on.
+pub fn store(data: &[u8]) -> Vec<u8> {
+    frame(CODEC_STORED, data.len() as u64, data)
+}
+
+/// Reverses [`compress`]. Rejects a frame whose restored length doesn't match the
+/// length it claims, which catches a corrupted or truncated frame deterministically
+/// instead of silently returning a partial message.
+pub fn decompress(frame: &[u8]) -> ResultStego<Vec<u8>> {
+    let mut byte_iter = frame.iter().copied();
+    let codec = byte_iter
+        .next()
+        .ok_or(StegoError::FailedToReceiveMessage)?;
+    let uncompressed_len = decode_varint(&mut byte_iter)? as usize;
+    let body: Vec<u8> = byte_iter.collect();
+
+    let data = match codec {
+        CODEC_STORED => body,
+        CODEC_SNAPPY => snap::raw::Decoder::new()
+            .decompress_vec(&body)
+            .map_err(|_| StegoError::FailedToReceiveMessage)?,
+        _ => return Err(StegoError::FailedToReceiveMessage),
+    };
+
+    if data.len() != uncompressed_len {
+        return Err(StegoError::FailedToReceiveMessage);
+    }
+
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_compressible_data() {
+        let data = vec![b'a'; 4096];
+        let frame = compress(&data);
+
+        assert_eq!(frame[0], CODEC_SNAPPY);
+        assert!(frame.len() < data.len());
+        assert_eq!(decompress(&frame).unwrap(), data);
+    }
+
+    #[test]
+    fn falls_back_to_stored_for_incompressible_data() {
+        let data: Vec<u8> = (0..=255u8).collect();
+        let frame = compress(&data);
+
+        assert_eq!(frame[0], CODEC_STORED);
+        assert_eq!(decompress(&frame).unwrap(), data);
+    }
+
+    #[test]
+    fn decompress_rejects_a_length_mismatch() {
+        let mut frame = vec![CODEC_STORED];
+        frame.extend(encode_varint(99));
+        frame.extend_from_slice(b"short");
+
+        assert!(decompress(&frame).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+//! Argon2id-derived XChaCha20-Poly1305 sealing for the embedded payload.
+//!
+//! [`encrypt`]'s `sealed` flag controls whether the AEAD step actually runs, but the
+//! envelope is always the same total size for a given plaintext length: a one-byte
+//! tag distinguishes the two shapes, so [`OVERHEAD_LEN`] — and every format's
+//! header-size/capacity math built on it — stays a fixed constant regardless of
+//! which requests opt in. An unsealed envelope's salt/nonce/tag-sized regions are
+//! just zeroed filler; [`decrypt`] reads the tag byte and skips the AEAD open
+//! entirely when it's unset.
+
+use crate::error::StegoError;
+use crate::object::ResultStego;
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use rand::rngs::OsRng;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+const TAG_LEN: usize = 16;
+
+/// Leading envelope byte recording whether the rest was AEAD-sealed.
+const SEALED_TAG: u8 = 1;
+const UNSEALED_TAG: u8 = 0;
+
+/// Fixed overhead [`encrypt`] adds on top of the plaintext: the seal tag, salt,
+/// nonce and AEAD tag — present whether or not `sealed` is set, so capacity math
+/// never has to branch on it.
+pub const OVERHEAD_LEN: usize = 1 + SALT_LEN + NONCE_LEN + TAG_LEN;
+
+fn derive_key(password: &str, salt: &[u8]) -> ResultStego<[u8; KEY_LEN]> {
+    let mut key = [0u8; KEY_LEN];
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|err| StegoError::Other(format!("Key derivation failed: {err}")))?;
+
+    Ok(key)
+}
+
+/// Seals `plaintext` with a key derived from `password` using Argon2id when
+/// `sealed` is `true`, returning the self-contained envelope `tag(1) || salt(16)
+/// || nonce(24) || ciphertext || tag(16)` that the caller embeds verbatim via LSB
+/// substitution.
+///
+/// When `sealed` is `false` (the backward-compatible default every caller used
+/// before the `encrypt` flag existed), the salt/nonce/AEAD-tag regions are left
+/// zeroed and `plaintext` is copied through unchanged — no key derivation or
+/// ciphering happens — but the envelope is still exactly [`OVERHEAD_LEN`] bytes
+/// larger than `plaintext`, so a format's capacity/header math never needs to know
+/// which path a given message took.
+pub fn encrypt(password: &str, plaintext: &[u8], sealed: bool) -> ResultStego<Vec<u8>> {
+    let mut envelope = Vec::with_capacity(OVERHEAD_LEN + plaintext.len());
+
+    if !sealed {
+        envelope.push(UNSEALED_TAG);
+        envelope.resize(1 + SALT_LEN + NONCE_LEN, 0);
+        envelope.extend_from_slice(plaintext);
+        envelope.resize(envelope.len() + TAG_LEN, 0);
+        return Ok(envelope);
+    }
+
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+
+    let key = derive_key(password, &salt)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+    let ciphertext = cipher
+        .encrypt(XNonce::from_slice(&nonce_bytes), plaintext)
+        .map_err(|_| StegoError::Other("Failed to encrypt payload".to_string()))?;
+
+    envelope.push(SEALED_TAG);
+    envelope.extend_from_slice(&salt);
+    envelope.extend_from_slice(&nonce_bytes);
+    envelope.extend_from_slice(&ciphertext);
+
+    Ok(envelope)
+}
+
+/// Reverses [`encrypt`], auto-detecting from the envelope's leading tag byte
+/// whether it needs to derive a key and verify the AEAD tag at all.
+///
+/// Returns [`StegoError::IncorrectPassword`] when a sealed envelope's AEAD tag
+/// does not verify. A wrong password and a corrupted/tampered carrier are
+/// indistinguishable from the tag alone, so both surface as the same
+/// deterministic error rather than leaving the caller to guess which one
+/// happened. An envelope too short to have come from `encrypt` also reports
+/// [`StegoError::IncorrectPassword`], the same as a failed tag check, instead of
+/// a distinct "malformed envelope" error the caller would have to handle separately.
+pub fn decrypt(password: &str, envelope: &[u8]) -> ResultStego<Vec<u8>> {
+    let Some((&tag, rest)) = envelope.split_first() else {
+        return Err(StegoError::IncorrectPassword);
+    };
+
+    if rest.len() < SALT_LEN + NONCE_LEN + TAG_LEN {
+        return Err(StegoError::IncorrectPassword);
+    }
+
+    if tag == UNSEALED_TAG {
+        let (_salt_and_nonce, rest) = rest.split_at(SALT_LEN + NONCE_LEN);
+        let plaintext = &rest[..rest.len() - TAG_LEN];
+        return Ok(plaintext.to_vec());
+    }
+
+    let (salt, rest) = rest.split_at(SALT_LEN);
+    let (nonce_bytes, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let key = derive_key(password, salt)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+
+    cipher
+        .decrypt(XNonce::from_slice(nonce_bytes), ciphertext)
+        .map_err(|_| StegoError::IncorrectPassword)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_decrypt_roundtrip() {
+        let envelope = encrypt("correct horse", b"secret message", true).unwrap();
+        let plaintext = decrypt("correct horse", &envelope).unwrap();
+
+        assert_eq!(plaintext, b"secret message");
+    }
+
+    #[test]
+    fn decrypt_with_wrong_password_fails() {
+        let envelope = encrypt("correct horse", b"secret message", true).unwrap();
+
+        assert!(matches!(
+            decrypt("battery staple", &envelope),
+            Err(StegoError::IncorrectPassword)
+        ));
+    }
+
+    #[test]
+    fn unsealed_roundtrip_skips_encryption() {
+        let envelope = encrypt("unused", b"secret message", false).unwrap();
+        let plaintext = decrypt("any password at all", &envelope).unwrap();
+
+        assert_eq!(plaintext, b"secret message");
+        assert_eq!(envelope.len(), OVERHEAD_LEN + "secret message".len());
+    }
+}
@@ -11,16 +11,33 @@ pub enum StegoError {
     #[error("{0}")]
     HoundError(#[from] hound::Error),
 
+    #[error("{0}")]
+    IoError(#[from] std::io::Error),
+
     #[error("Error password is incorrect")]
     IncorrectPassword,
 
     #[error("Could not receive message, file may be corrupted")]
     FailedToReceiveMessage,
 
+    #[error("Integrity check failed: wrong password or corrupted carrier")]
+    IntegrityCheckFailed,
+
     #[error("{0}")]
     Other(String),
 }
 
+/// Error produced while resolving a format string to a concrete codec in
+/// [`crate::formats::get_stego_by_str`].
+#[derive(Debug, Error)]
+pub enum GetStegoError {
+    #[error("Failed to build stego encoder: {0}")]
+    BuildStegoError(String),
+
+    #[error("No stego encoder registered for this format")]
+    StegoNotFoundError,
+}
+
 #[derive(Debug, Error)]
 pub enum StegoWaveClientError {
     #[error("Connection failed")]
@@ -0,0 +1,337 @@
+//! A steganography encoder/decoder for FLAC audio files.
+//!
+//! Unlike the WAV formats in [`crate::formats::wav`], a FLAC stream doesn't commit to
+//! a single bit depth up front, so [`FLAC`] decodes every depth through [`claxon`] into
+//! the same widened `i32` samples, embeds the message in their low bits exactly like
+//! [`crate::formats::wav::WAV24`]/[`crate::formats::wav::WAV32`] via [`StegoSample`],
+//! and re-encodes losslessly through [`flac_bound`] (a binding over `libFLAC`) so the
+//! embedded bits survive the round-trip instead of being smoothed away by lossy
+//! re-compression.
+
+use crate::configuration::Settings;
+use crate::crypto;
+use crate::error::StegoError;
+use crate::formats::wav::{
+    CHECKSUM_LEN, FLAC_FORMAT_ID, check_capacity, checksum, clear_bits, header_and_crypto_overhead,
+    read_header, write_bits,
+};
+use crate::object::{
+    AudioFileSpec, AudioSteganography, ByteIterator, FlacSpec, HEADER_LSB_DEEP, PayloadHeader,
+    ResultStego, StegoSample, UniqueRandomIndices,
+};
+use derive_builder::Builder;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+/// `lsb_deep` ceiling for [`FLAC`]. Every depth is decoded into `i32`, so this is the
+/// same width-driven cap as [`crate::formats::wav::WAV32`]; [`FLAC::validate_file`]
+/// additionally rejects a `lsb_deep` deeper than the *source* stream's own bit depth,
+/// which is only known once the file itself is read.
+const MAX_LSB_DEEP_FLAC: u8 = 32;
+
+/// A steganography encoder/decoder for FLAC audio files.
+///
+/// This struct provides methods for hiding and extracting messages in lossless FLAC
+/// files, decoding to PCM, embedding, and re-encoding losslessly so nothing is lost.
+///
+/// # Examples
+///
+/// ```rust
+/// # use stego_wave::{formats::flac::FLAC, AudioSteganography, configuration::Settings};
+/// let flac = FLAC::builder().lsb_deep(1).settings(Settings::new("../sw_config.toml").unwrap()).build().unwrap();
+/// ```
+#[derive(Builder, Debug, PartialEq)]
+#[builder(build_fn(validate = "Self::validate"))]
+#[builder(name = "FLACBuilder")]
+pub struct FLAC {
+    lsb_deep: u8,
+    #[builder(default)]
+    settings: Settings,
+}
+
+impl Default for FLAC {
+    fn default() -> Self {
+        let setting = Settings::default();
+        FLAC::builder()
+            .lsb_deep(setting.stego_wave_lib.default_lsb_deep.min(MAX_LSB_DEEP_FLAC))
+            .settings(setting)
+            .build()
+            .unwrap()
+    }
+}
+
+impl FLACBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(ref lsb_deep) = self.lsb_deep {
+            match *lsb_deep {
+                ld if ld == 0 || ld > MAX_LSB_DEEP_FLAC => Err(format!(
+                    "lsb_deep must be between 1 and {MAX_LSB_DEEP_FLAC}"
+                )),
+                _ => Ok(()),
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl FLAC {
+    fn max_occupancy(&self) -> usize {
+        self.settings.stego_wave_lib.max_occupancy
+    }
+
+    fn decode(byte: Vec<u8>) -> ResultStego<(Vec<i32>, FlacSpec)> {
+        let mut reader = claxon::FlacReader::new(Cursor::new(byte))
+            .map_err(|_| StegoError::Other("Error reading FLAC".to_string()))?;
+
+        let stream_info = reader.streaminfo();
+        let spec = FlacSpec {
+            channels: stream_info.channels,
+            sample_rate: stream_info.sample_rate,
+            bits_per_sample: stream_info.bits_per_sample,
+        };
+
+        let samples: Vec<i32> = reader
+            .samples()
+            .collect::<Result<_, _>>()
+            .map_err(|_| StegoError::Other("Error reading samples".to_string()))?;
+
+        Ok((samples, spec))
+    }
+
+    fn encode(spec: FlacSpec, samples: &[i32]) -> ResultStego<Vec<u8>> {
+        let mut out_buf = Vec::<u8>::new();
+
+        let mut encoder = flac_bound::FlacEncoder::new()
+            .ok_or_else(|| StegoError::Other("Error creating FLAC encoder".to_string()))?
+            .channels(spec.channels)
+            .bits_per_sample(spec.bits_per_sample)
+            .sample_rate(spec.sample_rate)
+            .init_write(&mut out_buf)
+            .map_err(|_| StegoError::Other("Error initializing FLAC encoder".to_string()))?;
+
+        encoder
+            .process_interleaved(samples, (samples.len() as u32) / spec.channels)
+            .map_err(|_| StegoError::Other("Error encoding FLAC samples".to_string()))?;
+
+        encoder
+            .finish()
+            .map_err(|_| StegoError::Other("Error finalizing FLAC encoder".to_string()))?;
+
+        Ok(out_buf)
+    }
+}
+
+impl AudioSteganography<i32> for FLAC {
+    type Builder = FLACBuilder;
+
+    fn builder() -> Self::Builder {
+        FLACBuilder::default()
+    }
+
+    fn hide_message(
+        &self,
+        file_input: impl Into<PathBuf>,
+        file_output: impl Into<PathBuf>,
+        message: impl Into<String>,
+        password: impl Into<String>,
+        encrypt: bool,
+    ) -> ResultStego<()> {
+        let input_path = file_input.into();
+        let output_path = file_output.into();
+
+        self.validate_file(&input_path)?;
+        let byte = std::fs::read(&input_path)?;
+        let (mut samples, spec) = Self::decode(byte)?;
+
+        self.hide_message_binary(&mut samples, &message.into(), &password.into(), encrypt)?;
+
+        let out_buf = Self::encode(spec, &samples)?;
+        std::fs::write(output_path, out_buf)?;
+        Ok(())
+    }
+
+    fn hide_message_binary(
+        &self,
+        samples: &mut [i32],
+        message: &str,
+        password: &str,
+        encrypt: bool,
+    ) -> ResultStego<()> {
+        let message_bytes = message.as_bytes();
+        let mut plaintext = Vec::with_capacity(CHECKSUM_LEN + message_bytes.len());
+        plaintext.extend_from_slice(&checksum(message_bytes));
+        plaintext.extend_from_slice(message_bytes);
+
+        let envelope = crypto::encrypt(password, &plaintext, encrypt)?;
+        let header_bytes = PayloadHeader {
+            lsb_deep: self.lsb_deep,
+            format_id: self.format_id(),
+            payload_len: envelope.len() as u64,
+        }
+        .encode();
+
+        check_capacity(
+            header_bytes.len(),
+            envelope.len(),
+            samples.len(),
+            self.lsb_deep,
+            self.max_occupancy(),
+        )?;
+
+        let mut indices_iter =
+            UniqueRandomIndices::new(samples.len(), password, self.max_occupancy());
+
+        write_bits(
+            samples,
+            &mut indices_iter,
+            HEADER_LSB_DEEP,
+            header_bytes.into_iter(),
+        );
+        write_bits(samples, &mut indices_iter, self.lsb_deep, envelope.into_iter());
+
+        Ok(())
+    }
+
+    fn extract_message(
+        &self,
+        file: impl Into<PathBuf>,
+        password: impl Into<String>,
+    ) -> ResultStego<String> {
+        let input_path = file.into();
+        self.validate_file(&input_path)?;
+
+        let byte = std::fs::read(&input_path)?;
+        let (samples, _spec) = Self::decode(byte)?;
+
+        self.extract_message_binary(&samples, &password.into())
+    }
+
+    fn extract_message_binary(&self, samples: &[i32], password: &str) -> ResultStego<String> {
+        let mut indices_iter =
+            UniqueRandomIndices::new(samples.len(), password, self.max_occupancy());
+
+        let header = read_header(
+            samples,
+            &mut indices_iter,
+            samples.len(),
+            self.max_occupancy(),
+            MAX_LSB_DEEP_FLAC,
+        )?;
+
+        let mut payload_iter = ByteIterator::new(
+            samples,
+            &mut indices_iter,
+            i32::mask_for(header.lsb_deep),
+            header.lsb_deep,
+            0,
+            0,
+        );
+
+        let envelope: Vec<u8> = (&mut payload_iter)
+            .take(header.payload_len as usize)
+            .collect();
+        if envelope.len() != header.payload_len as usize {
+            return Err(StegoError::FailedToReceiveMessage);
+        }
+
+        let plaintext = crypto::decrypt(password, &envelope)?;
+        if plaintext.len() < CHECKSUM_LEN {
+            return Err(StegoError::IntegrityCheckFailed);
+        }
+        let (checksum_bytes, message_bytes) = plaintext.split_at(CHECKSUM_LEN);
+
+        if checksum_bytes != checksum(message_bytes) {
+            return Err(StegoError::IntegrityCheckFailed);
+        }
+
+        String::from_utf8(message_bytes.to_vec()).map_err(|_| StegoError::FailedToReceiveMessage)
+    }
+
+    fn clear_secret_message(&self, file: impl Into<PathBuf>, password: &str) -> ResultStego<()> {
+        let input_path = file.into();
+        self.validate_file(&input_path)?;
+
+        let byte = std::fs::read(&input_path)?;
+        let (mut samples, spec) = Self::decode(byte)?;
+
+        self.clear_secret_message_binary(&mut samples, password)?;
+
+        let out_buf = Self::encode(spec, &samples)?;
+        std::fs::write(input_path, out_buf)?;
+
+        Ok(())
+    }
+
+    fn clear_secret_message_binary(&self, samples: &mut [i32], password: &str) -> ResultStego<()> {
+        let mut indices_iter =
+            UniqueRandomIndices::new(samples.len(), password, self.max_occupancy());
+
+        let header = {
+            let mut header_probe = indices_iter.clone();
+            read_header(
+                samples,
+                &mut header_probe,
+                samples.len(),
+                self.max_occupancy(),
+                MAX_LSB_DEEP_FLAC,
+            )?
+        };
+        let header_len = header.encode().len();
+
+        clear_bits(samples, &mut indices_iter, HEADER_LSB_DEEP, header_len);
+        clear_bits(
+            samples,
+            &mut indices_iter,
+            header.lsb_deep,
+            header.payload_len as usize,
+        );
+
+        Ok(())
+    }
+
+    fn validate_file(&self, file: &Path) -> ResultStego<()> {
+        let byte = std::fs::read(file)?;
+        let reader = claxon::FlacReader::new(Cursor::new(byte))
+            .map_err(|_| StegoError::InvalidFile("Only FLAC file supported".to_string()))?;
+
+        if self.lsb_deep > reader.streaminfo().bits_per_sample as u8 {
+            return Err(StegoError::InvalidFile(
+                "lsb_deep exceeds the source file's bit depth".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn read_samples_from_byte(&self, byte: Vec<u8>) -> ResultStego<(Vec<i32>, AudioFileSpec)> {
+        let (samples, spec) = Self::decode(byte)?;
+        Ok((samples, AudioFileSpec::Flac(spec)))
+    }
+
+    fn write_samples_to_byte(&self, spec: AudioFileSpec, samples: &[i32]) -> ResultStego<Vec<u8>> {
+        let spec = match spec {
+            AudioFileSpec::Flac(spec) => spec,
+            _ => return Err(StegoError::Other("Expected a FLAC spec".to_string())),
+        };
+
+        Self::encode(spec, samples)
+    }
+
+    fn default_filename(&self) -> String {
+        "flac.flac".to_string()
+    }
+
+    fn format_id(&self) -> u8 {
+        FLAC_FORMAT_ID
+    }
+
+    fn capacity_bytes(&self, sample_len: usize) -> usize {
+        let usable_bits = sample_len * self.max_occupancy() / 100 * self.lsb_deep as usize;
+        (usable_bits / 8).saturating_sub(header_and_crypto_overhead())
+    }
+
+    fn overhead_bytes(&self) -> usize {
+        header_and_crypto_overhead()
+    }
+}
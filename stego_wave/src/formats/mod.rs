@@ -1,26 +1,305 @@
 use crate::AudioSteganography;
 use crate::configuration::{Settings, StegoWaveLib};
-use crate::error::GetStegoError;
-use crate::formats::wav::WAV16;
+use crate::error::{GetStegoError, StegoError};
+use crate::formats::flac::FLAC;
+use crate::formats::wav::{WAV8, WAV16, WAV24, WAV32, WAV32F};
+use crate::object::{AudioFileSpec, ResultStego, StegoSamples};
 
+pub mod flac;
 pub mod wav;
 
+/// A concrete steganography codec selected by format string.
+///
+/// Each variant picks its own sample width (`i8`/`i16`/`i32`) via its
+/// [`AudioSteganography`] impl, so this enum erases that width behind
+/// [`StegoSamples`] instead: every gateway only knows the format as a string at
+/// request time, and drives the same read/hide/extract/clear/write pipeline
+/// regardless of which width [`get_stego_by_str`] picked.
+pub enum StegoFormat {
+    Wav8(WAV8),
+    Wav16(WAV16),
+    Wav24(WAV24),
+    Wav32(WAV32),
+    Wav32F(WAV32F),
+    Flac(FLAC),
+}
+
+/// Error returned when `samples` doesn't carry the sample width the selected
+/// format expects. Never happens as long as `samples` came from this same
+/// [`StegoFormat`]'s own [`StegoFormat::read_samples_from_byte`].
+fn mismatched_samples() -> StegoError {
+    StegoError::Other("Sample width does not match the selected format".to_string())
+}
+
+impl StegoFormat {
+    pub fn read_samples_from_byte(
+        &self,
+        byte: Vec<u8>,
+    ) -> ResultStego<(StegoSamples, AudioFileSpec)> {
+        match self {
+            StegoFormat::Wav8(stego) => stego
+                .read_samples_from_byte(byte)
+                .map(|(samples, spec)| (StegoSamples::I8(samples), spec)),
+            StegoFormat::Wav16(stego) => stego
+                .read_samples_from_byte(byte)
+                .map(|(samples, spec)| (StegoSamples::I16(samples), spec)),
+            StegoFormat::Wav24(stego) => stego
+                .read_samples_from_byte(byte)
+                .map(|(samples, spec)| (StegoSamples::I32(samples), spec)),
+            StegoFormat::Wav32(stego) => stego
+                .read_samples_from_byte(byte)
+                .map(|(samples, spec)| (StegoSamples::I32(samples), spec)),
+            StegoFormat::Wav32F(stego) => stego
+                .read_samples_from_byte(byte)
+                .map(|(samples, spec)| (StegoSamples::F32(samples), spec)),
+            StegoFormat::Flac(stego) => stego
+                .read_samples_from_byte(byte)
+                .map(|(samples, spec)| (StegoSamples::I32(samples), spec)),
+        }
+    }
+
+    pub fn write_samples_to_byte(
+        &self,
+        spec: AudioFileSpec,
+        samples: &StegoSamples,
+    ) -> ResultStego<Vec<u8>> {
+        match (self, samples) {
+            (StegoFormat::Wav8(stego), StegoSamples::I8(samples)) => {
+                stego.write_samples_to_byte(spec, samples)
+            }
+            (StegoFormat::Wav16(stego), StegoSamples::I16(samples)) => {
+                stego.write_samples_to_byte(spec, samples)
+            }
+            (StegoFormat::Wav24(stego), StegoSamples::I32(samples)) => {
+                stego.write_samples_to_byte(spec, samples)
+            }
+            (StegoFormat::Wav32(stego), StegoSamples::I32(samples)) => {
+                stego.write_samples_to_byte(spec, samples)
+            }
+            (StegoFormat::Wav32F(stego), StegoSamples::F32(samples)) => {
+                stego.write_samples_to_byte(spec, samples)
+            }
+            (StegoFormat::Flac(stego), StegoSamples::I32(samples)) => {
+                stego.write_samples_to_byte(spec, samples)
+            }
+            _ => Err(mismatched_samples()),
+        }
+    }
+
+    pub fn hide_message_binary(
+        &self,
+        samples: &mut StegoSamples,
+        message: &str,
+        password: &str,
+        encrypt: bool,
+    ) -> ResultStego<()> {
+        match (self, samples) {
+            (StegoFormat::Wav8(stego), StegoSamples::I8(samples)) => {
+                stego.hide_message_binary(samples, message, password, encrypt)
+            }
+            (StegoFormat::Wav16(stego), StegoSamples::I16(samples)) => {
+                stego.hide_message_binary(samples, message, password, encrypt)
+            }
+            (StegoFormat::Wav24(stego), StegoSamples::I32(samples)) => {
+                stego.hide_message_binary(samples, message, password, encrypt)
+            }
+            (StegoFormat::Wav32(stego), StegoSamples::I32(samples)) => {
+                stego.hide_message_binary(samples, message, password, encrypt)
+            }
+            (StegoFormat::Wav32F(stego), StegoSamples::F32(samples)) => {
+                stego.hide_message_binary(samples, message, password, encrypt)
+            }
+            (StegoFormat::Flac(stego), StegoSamples::I32(samples)) => {
+                stego.hide_message_binary(samples, message, password, encrypt)
+            }
+            _ => Err(mismatched_samples()),
+        }
+    }
+
+    /// Like [`Self::hide_message_binary`], but calls `on_progress(processed, total)`
+    /// as samples are embedded into, for gateways that stream progress back to the
+    /// caller (see `ws_process` in the REST service crate). See
+    /// [`AudioSteganography::hide_message_binary_with_progress`] for which formats
+    /// report real incremental progress versus a single 0%/100% jump.
+    pub fn hide_message_binary_with_progress(
+        &self,
+        samples: &mut StegoSamples,
+        message: &str,
+        password: &str,
+        encrypt: bool,
+        on_progress: &mut dyn FnMut(usize, usize),
+    ) -> ResultStego<()> {
+        match (self, samples) {
+            (StegoFormat::Wav8(stego), StegoSamples::I8(samples)) => stego
+                .hide_message_binary_with_progress(samples, message, password, encrypt, on_progress),
+            (StegoFormat::Wav16(stego), StegoSamples::I16(samples)) => stego
+                .hide_message_binary_with_progress(samples, message, password, encrypt, on_progress),
+            (StegoFormat::Wav24(stego), StegoSamples::I32(samples)) => stego
+                .hide_message_binary_with_progress(samples, message, password, encrypt, on_progress),
+            (StegoFormat::Wav32(stego), StegoSamples::I32(samples)) => stego
+                .hide_message_binary_with_progress(samples, message, password, encrypt, on_progress),
+            (StegoFormat::Wav32F(stego), StegoSamples::F32(samples)) => stego
+                .hide_message_binary_with_progress(samples, message, password, encrypt, on_progress),
+            (StegoFormat::Flac(stego), StegoSamples::I32(samples)) => stego
+                .hide_message_binary_with_progress(samples, message, password, encrypt, on_progress),
+            _ => Err(mismatched_samples()),
+        }
+    }
+
+    pub fn extract_message_binary(
+        &self,
+        samples: &StegoSamples,
+        password: &str,
+    ) -> ResultStego<String> {
+        match (self, samples) {
+            (StegoFormat::Wav8(stego), StegoSamples::I8(samples)) => {
+                stego.extract_message_binary(samples, password)
+            }
+            (StegoFormat::Wav16(stego), StegoSamples::I16(samples)) => {
+                stego.extract_message_binary(samples, password)
+            }
+            (StegoFormat::Wav24(stego), StegoSamples::I32(samples)) => {
+                stego.extract_message_binary(samples, password)
+            }
+            (StegoFormat::Wav32(stego), StegoSamples::I32(samples)) => {
+                stego.extract_message_binary(samples, password)
+            }
+            (StegoFormat::Wav32F(stego), StegoSamples::F32(samples)) => {
+                stego.extract_message_binary(samples, password)
+            }
+            (StegoFormat::Flac(stego), StegoSamples::I32(samples)) => {
+                stego.extract_message_binary(samples, password)
+            }
+            _ => Err(mismatched_samples()),
+        }
+    }
+
+    pub fn clear_secret_message_binary(
+        &self,
+        samples: &mut StegoSamples,
+        password: &str,
+    ) -> ResultStego<()> {
+        match (self, samples) {
+            (StegoFormat::Wav8(stego), StegoSamples::I8(samples)) => {
+                stego.clear_secret_message_binary(samples, password)
+            }
+            (StegoFormat::Wav16(stego), StegoSamples::I16(samples)) => {
+                stego.clear_secret_message_binary(samples, password)
+            }
+            (StegoFormat::Wav24(stego), StegoSamples::I32(samples)) => {
+                stego.clear_secret_message_binary(samples, password)
+            }
+            (StegoFormat::Wav32(stego), StegoSamples::I32(samples)) => {
+                stego.clear_secret_message_binary(samples, password)
+            }
+            (StegoFormat::Wav32F(stego), StegoSamples::F32(samples)) => {
+                stego.clear_secret_message_binary(samples, password)
+            }
+            (StegoFormat::Flac(stego), StegoSamples::I32(samples)) => {
+                stego.clear_secret_message_binary(samples, password)
+            }
+            _ => Err(mismatched_samples()),
+        }
+    }
+
+    pub fn default_filename(&self) -> String {
+        match self {
+            StegoFormat::Wav8(stego) => stego.default_filename(),
+            StegoFormat::Wav16(stego) => stego.default_filename(),
+            StegoFormat::Wav24(stego) => stego.default_filename(),
+            StegoFormat::Wav32(stego) => stego.default_filename(),
+            StegoFormat::Wav32F(stego) => stego.default_filename(),
+            StegoFormat::Flac(stego) => stego.default_filename(),
+        }
+    }
+
+    /// Maximum number of message bytes that fit in `sample_len` samples, after the
+    /// header and encryption overhead ([`StegoFormat::overhead_bytes`]) is accounted for.
+    pub fn capacity_bytes(&self, sample_len: usize) -> usize {
+        match self {
+            StegoFormat::Wav8(stego) => stego.capacity_bytes(sample_len),
+            StegoFormat::Wav16(stego) => stego.capacity_bytes(sample_len),
+            StegoFormat::Wav24(stego) => stego.capacity_bytes(sample_len),
+            StegoFormat::Wav32(stego) => stego.capacity_bytes(sample_len),
+            StegoFormat::Wav32F(stego) => stego.capacity_bytes(sample_len),
+            StegoFormat::Flac(stego) => stego.capacity_bytes(sample_len),
+        }
+    }
+
+    /// Fixed number of bytes consumed by the header and encryption envelope on top
+    /// of the message itself, regardless of carrier size.
+    pub fn overhead_bytes(&self) -> usize {
+        match self {
+            StegoFormat::Wav8(stego) => stego.overhead_bytes(),
+            StegoFormat::Wav16(stego) => stego.overhead_bytes(),
+            StegoFormat::Wav24(stego) => stego.overhead_bytes(),
+            StegoFormat::Wav32(stego) => stego.overhead_bytes(),
+            StegoFormat::Wav32F(stego) => stego.overhead_bytes(),
+            StegoFormat::Flac(stego) => stego.overhead_bytes(),
+        }
+    }
+
+    /// Reads `byte` as a carrier file and reports `(capacity_bytes, overhead_bytes)`
+    /// so callers can validate a message fits before attempting to hide it.
+    pub fn read_capacity_from_byte(&self, byte: Vec<u8>) -> ResultStego<(usize, usize)> {
+        let (samples, _spec) = self.read_samples_from_byte(byte)?;
+        Ok((self.capacity_bytes(samples.len()), self.overhead_bytes()))
+    }
+}
+
+/// Resolves `format` to a concrete codec, configured with `lsb_deep` and `compress`.
+///
+/// `compress` is only honored by `WAV16` (see [`crate::compression`]); other
+/// formats silently ignore it, the same way they ignore `WAV16`-only knobs like
+/// channel-aware embedding.
 pub fn get_stego_by_str(
     format: &str,
     lsb_deep: u8,
+    compress: bool,
     settings: StegoWaveLib,
-) -> Result<impl AudioSteganography<i16>, GetStegoError> {
+) -> Result<StegoFormat, GetStegoError> {
+    let settings = Settings {
+        stego_wave_lib: settings,
+    };
+
     match format {
-        "wav16" => match WAV16::builder()
+        "wav8" => WAV8::builder()
+            .lsb_deep(lsb_deep)
+            .settings(settings)
+            .build()
+            .map(StegoFormat::Wav8)
+            .map_err(|err| GetStegoError::BuildStegoError(err.to_string())),
+        "wav16" => WAV16::builder()
+            .lsb_deep(lsb_deep)
+            .compress(compress)
+            .settings(settings)
+            .build()
+            .map(StegoFormat::Wav16)
+            .map_err(|err| GetStegoError::BuildStegoError(err.to_string())),
+        "wav24" => WAV24::builder()
+            .lsb_deep(lsb_deep)
+            .settings(settings)
+            .build()
+            .map(StegoFormat::Wav24)
+            .map_err(|err| GetStegoError::BuildStegoError(err.to_string())),
+        "wav32" => WAV32::builder()
+            .lsb_deep(lsb_deep)
+            .settings(settings)
+            .build()
+            .map(StegoFormat::Wav32)
+            .map_err(|err| GetStegoError::BuildStegoError(err.to_string())),
+        "wav32f" => WAV32F::builder()
+            .lsb_deep(lsb_deep)
+            .settings(settings)
+            .build()
+            .map(StegoFormat::Wav32F)
+            .map_err(|err| GetStegoError::BuildStegoError(err.to_string())),
+        "flac" => FLAC::builder()
             .lsb_deep(lsb_deep)
-            .settings(Settings {
-                stego_wave_lib: settings,
-            })
+            .settings(settings)
             .build()
-        {
-            Ok(wav16) => Ok(wav16),
-            Err(err) => Err(GetStegoError::BuildStegoError(err.to_string())),
-        },
+            .map(StegoFormat::Flac)
+            .map_err(|err| GetStegoError::BuildStegoError(err.to_string())),
         _ => Err(GetStegoError::StegoNotFoundError),
     }
 }
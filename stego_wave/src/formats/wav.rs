@@ -1,13 +1,186 @@
+use crate::compression;
 use crate::configuration::Settings;
+use crate::crypto;
 use crate::error::StegoError;
+use crate::riff;
 use crate::object::{
-    AudioFileSpec, AudioSteganography, ByteIterator, ResultStego, UniqueRandomIndices,
+    AudioFileSpec, AudioSteganography, ByteIterator, ChannelIndices, EmbedChannel, HEADER_LSB_DEEP,
+    HEADER_MAX_LEN, PayloadHeader, ProgressIndices, ResultStego, StegoSample, UniqueRandomIndices,
 };
 use derive_builder::Builder;
 use std::io::Cursor;
-use std::iter;
 use std::path::{Path, PathBuf};
 
+/// Format id embedded in the payload header, identifying this codec to auto-detecting readers.
+const WAV8_FORMAT_ID: u8 = 1;
+const WAV16_FORMAT_ID: u8 = 0;
+const WAV24_FORMAT_ID: u8 = 2;
+const WAV32_FORMAT_ID: u8 = 3;
+const WAV32F_FORMAT_ID: u8 = 4;
+pub(crate) const FLAC_FORMAT_ID: u8 = 5;
+
+/// `lsb_deep` ceiling for [`WAV32F`]. The mantissa is 23 bits wide, but embedding
+/// deep into it shifts a sample's value enough to be audible, so the usable range
+/// is capped well short of that.
+const MAX_LSB_DEEP_F32: u8 = 8;
+
+/// Bytes of the truncated BLAKE3 checksum prepended to the message before encryption.
+pub(crate) const CHECKSUM_LEN: usize = 8;
+
+pub(crate) fn checksum(data: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let mut checksum = [0u8; CHECKSUM_LEN];
+    checksum.copy_from_slice(&blake3::hash(data).as_bytes()[..CHECKSUM_LEN]);
+    checksum
+}
+
+/// Fixed cost, in bytes, of the header and encryption envelope that accompany
+/// every hidden message regardless of its length. Identical across bit depths:
+/// the header format and AEAD envelope don't depend on the carrier's sample width.
+/// Slightly over-counts for formats other than [`WAV16`], the only one that wraps
+/// its message in a [`compression`] frame, but a conservative capacity estimate
+/// never reports more room than is actually there.
+pub(crate) fn header_and_crypto_overhead() -> usize {
+    HEADER_MAX_LEN + crypto::OVERHEAD_LEN + CHECKSUM_LEN + compression::MAX_FRAME_OVERHEAD
+}
+
+/// Checks that `header_bytes` (always embedded at [`HEADER_LSB_DEEP`]) and
+/// `payload_bytes` (embedded at `lsb_deep`) both fit within `max_occupancy`.
+pub(crate) fn check_capacity(
+    header_bytes: usize,
+    payload_bytes: usize,
+    samples_len: usize,
+    lsb_deep: u8,
+    max_occupancy: usize,
+) -> ResultStego<()> {
+    let header_samples = (header_bytes * 8).div_ceil(HEADER_LSB_DEEP as usize);
+    let payload_samples = (payload_bytes * 8).div_ceil(lsb_deep as usize);
+    let required_samples = (header_samples + payload_samples) * 100 / max_occupancy;
+
+    if required_samples > samples_len {
+        return Err(StegoError::NotEnoughSamples(required_samples + 1));
+    }
+
+    Ok(())
+}
+
+fn read_samples<S: hound::Sample>(
+    reader: &mut hound::WavReader<impl std::io::Read>,
+) -> ResultStego<Vec<S>> {
+    reader
+        .samples::<S>()
+        .map(|s| s.map_err(StegoError::from))
+        .collect()
+}
+
+/// Writes `bytes` into `samples`, consuming `indices_iter` at `lsb_deep` bits per sample.
+pub(crate) fn write_bits<S: StegoSample>(
+    samples: &mut [S],
+    indices_iter: &mut impl Iterator<Item = usize>,
+    lsb_deep: u8,
+    bytes: impl Iterator<Item = u8>,
+) {
+    let mask = S::mask_for(lsb_deep);
+    let mut bits = bytes.flat_map(|byte| (0..8).rev().map(move |shift| (byte >> shift) & 1));
+
+    let mut write_full = false;
+    for sample_index in indices_iter {
+        let mut value: u32 = 0;
+        for _ in 0..lsb_deep {
+            value = (value << 1)
+                | match bits.next() {
+                    Some(bit) => bit as u32,
+                    None => {
+                        write_full = true;
+                        0u32
+                    }
+                };
+        }
+
+        samples[sample_index] = samples[sample_index].with_low_bits(mask, value);
+        if write_full {
+            break;
+        }
+    }
+}
+
+/// Zeroes the low `lsb_deep` bits of the next `byte_count` bytes' worth of samples.
+pub(crate) fn clear_bits<S: StegoSample>(
+    samples: &mut [S],
+    indices_iter: &mut impl Iterator<Item = usize>,
+    lsb_deep: u8,
+    byte_count: usize,
+) {
+    let mask = S::mask_for(lsb_deep);
+    let mut bits_remaining = byte_count * 8;
+
+    for sample_index in indices_iter {
+        if bits_remaining == 0 {
+            break;
+        }
+
+        samples[sample_index] = samples[sample_index].with_low_bits(mask, 0);
+        bits_remaining = bits_remaining.saturating_sub(lsb_deep as usize);
+    }
+}
+
+/// Decodes the self-describing header from the front of `indices_iter`, which is always
+/// embedded at [`HEADER_LSB_DEEP`] regardless of the payload's `lsb_deep`. `max_lsb_deep`
+/// bounds the header's claimed `lsb_deep` to the calling format's valid range.
+pub(crate) fn read_header<S: StegoSample>(
+    samples: &[S],
+    indices_iter: &mut impl Iterator<Item = usize>,
+    samples_len: usize,
+    max_occupancy: usize,
+    max_lsb_deep: u8,
+) -> ResultStego<PayloadHeader> {
+    let mut header_iter = ByteIterator::new(
+        samples,
+        indices_iter,
+        S::mask_for(HEADER_LSB_DEEP),
+        HEADER_LSB_DEEP,
+        0,
+        0,
+    );
+
+    let max_payload_len = (samples_len * max_occupancy / 100) as u64 / 8;
+    let header = PayloadHeader::decode(&mut header_iter, max_payload_len)?;
+
+    if header.lsb_deep == 0 || header.lsb_deep > max_lsb_deep {
+        return Err(StegoError::FailedToReceiveMessage);
+    }
+
+    Ok(header)
+}
+
+/// Marks which samples are safe to use as carriers for [`WAV32F`]: `true` for every
+/// finite sample, `false` for NaN/±Infinity. Computed once up front from the
+/// unmodified samples, since embedding only ever touches mantissa bits and never
+/// a sample's sign/exponent, so finiteness is the same before and after embedding.
+fn finite_mask(samples: &[f32]) -> Vec<bool> {
+    samples.iter().map(|s| s.is_finite()).collect()
+}
+
+/// Filters an index iterator down to the indices `finite` marks as usable, so
+/// [`WAV32F`] never embeds into (or reads a data-dependent bit count out of) a
+/// NaN/±Infinity sample.
+struct FiniteIndices<'a, I> {
+    finite: &'a [bool],
+    inner: I,
+}
+
+impl<I: Iterator<Item = usize>> Iterator for FiniteIndices<'_, I> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        for index in self.inner.by_ref() {
+            if self.finite[index] {
+                return Some(index);
+            }
+        }
+        None
+    }
+}
+
 /// A steganography encoder/decoder for 16-bit WAV audio files.
 ///
 /// This struct provides methods for hiding and extracting messages in 16-bit WAV files.
@@ -25,6 +198,35 @@ pub struct WAV16 {
     lsb_deep: u8,
     #[builder(default)]
     settings: Settings,
+    /// Which interleaved channel(s) to embed into. Defaults to [`EmbedChannel::All`],
+    /// matching the original flat-buffer behavior.
+    #[builder(default)]
+    embed_channel: EmbedChannel,
+    /// The carrier's channel count, used to compute interleave stride for
+    /// [`EmbedChannel::Single`]/[`EmbedChannel::RoundRobin`]. Irrelevant under
+    /// [`EmbedChannel::All`]. Must match `reader.spec().channels` for the file
+    /// actually being processed.
+    #[builder(default = "1")]
+    channels: u16,
+    /// When set, `hide_message` accepts a cover file of any bit depth/format by
+    /// normalizing it to 16-bit PCM first (see [`WAV16::read_and_normalize`])
+    /// instead of rejecting it outright. Off by default, so the strict
+    /// `validate_file` behavior is unchanged unless explicitly opted into.
+    #[builder(default)]
+    normalize_input: bool,
+    /// When set, `hide_message`/`clear_secret_message` preserve the input file's
+    /// `LIST INFO` tags and `fact` chunk in the output instead of silently
+    /// dropping them the way a bare `hound::WavWriter` rewrite would. Off by
+    /// default; see [`crate::riff`].
+    #[builder(default)]
+    preserve_metadata: bool,
+    /// When set, the message is Snappy-compressed (see [`crate::compression`])
+    /// before encryption, raising effective capacity for compressible payloads.
+    /// Extraction always attempts decompression regardless of this flag, since
+    /// the compressed frame is self-describing; off by default so uncompressible
+    /// payloads don't pay the frame header for nothing.
+    #[builder(default)]
+    compress: bool,
 }
 
 impl Default for WAV16 {
@@ -47,7 +249,18 @@ impl WAV16Builder {
             }
         } else {
             Ok(())
+        }?;
+
+        let channels = self.channels.unwrap_or(1);
+        if let Some(EmbedChannel::Single(channel)) = self.embed_channel {
+            if channel >= channels {
+                return Err(format!(
+                    "embed_channel Single({channel}) is out of range for a {channels}-channel file"
+                ));
+            }
         }
+
+        Ok(())
     }
 }
 
@@ -56,65 +269,102 @@ impl WAV16 {
         self.settings.stego_wave_lib.max_occupancy
     }
 
-    fn header(&self) -> &str {
-        &self.settings.stego_wave_lib.header
-    }
-
-    fn is_enough_samples(&self, msg_len: usize, samples_len: usize) -> ResultStego<()> {
-        let msg_bits = msg_len * 8;
-        let total_bits = samples_len * (self.lsb_deep as usize) * self.max_occupancy() / 100;
-
-        if msg_bits > total_bits {
-            let required_bits = msg_bits * 100 / self.max_occupancy();
-            let required_samples = required_bits / (self.lsb_deep as usize);
-            return Err(StegoError::NotEnoughSamples(required_samples + 1));
-        }
-
-        Ok(())
-    }
-
-    fn read_sample(reader: &mut hound::WavReader<impl std::io::Read>) -> ResultStego<Vec<i16>> {
-        reader
-            .samples::<i16>()
-            .map(|s| s.map_err(StegoError::from))
-            .collect()
+    fn indices(&self, sample_len: usize, password: &str) -> ChannelIndices {
+        ChannelIndices::new(
+            sample_len,
+            self.channels,
+            self.embed_channel,
+            password,
+            self.max_occupancy(),
+        )
     }
 
-    #[inline]
-    fn get_mask(&self) -> i16 {
-        let mask: i32 = (1 << self.lsb_deep) - 1;
-        mask as i16
+    fn usable_sample_count(&self, sample_len: usize) -> usize {
+        ChannelIndices::usable_sample_count(sample_len, self.channels, self.embed_channel)
     }
 
-    fn validate_header<'a, I: Iterator<Item = usize>>(
+    /// Compresses/checksums/encrypts `message` and builds its header, checking the
+    /// result fits in `sample_len` samples. Shared by [`AudioSteganography::hide_message_binary`]
+    /// and [`AudioSteganography::hide_message_binary_with_progress`] so the two only
+    /// differ in how they drive the index iterator, not in how the envelope is built.
+    fn prepare_envelope(
         &self,
-        samples: &'a [i16],
-        indicates_iter: &'a mut I,
-    ) -> ResultStego<ByteIterator<'a, &'a mut I, i16>> {
-        let mut header_bytes = Vec::with_capacity(self.header().len());
+        message: &str,
+        password: &str,
+        encrypt: bool,
+        sample_len: usize,
+    ) -> ResultStego<(Vec<u8>, Vec<u8>)> {
+        let message_bytes = message.as_bytes();
+        let frame = if self.compress {
+            compression::compress(message_bytes)
+        } else {
+            compression::store(message_bytes)
+        };
+        let mut plaintext = Vec::with_capacity(CHECKSUM_LEN + frame.len());
+        plaintext.extend_from_slice(&checksum(&frame));
+        plaintext.extend_from_slice(&frame);
+
+        let envelope = crypto::encrypt(password, &plaintext, encrypt)?;
+        let header_bytes = PayloadHeader {
+            lsb_deep: self.lsb_deep,
+            format_id: self.format_id(),
+            payload_len: envelope.len() as u64,
+        }
+        .encode();
 
-        let mut byte_iterator = ByteIterator::new(
-            samples,
-            indicates_iter,
-            self.get_mask(),
+        check_capacity(
+            header_bytes.len(),
+            envelope.len(),
+            self.usable_sample_count(sample_len),
             self.lsb_deep,
-            0,
-            0,
-        );
+            self.max_occupancy(),
+        )?;
+
+        Ok((header_bytes, envelope))
+    }
 
-        for byte in &mut byte_iterator {
-            header_bytes.push(byte);
+    /// Reads `path` and normalizes its samples to 16-bit PCM, regardless of the
+    /// file's original bit depth/format, the way a general WAV decoder widens or
+    /// narrows PCM to a common working width (S8/S24/S32/F32 -> S16).
+    ///
+    /// This is lossy with respect to the original cover audio's resolution, but
+    /// the returned [`AudioFileSpec`] describes exactly the 16-bit PCM `samples`
+    /// it returns, so hiding into them and writing out with that spec produces a
+    /// self-consistent stego file: extraction never needs the original file.
+    pub fn read_and_normalize(path: impl AsRef<Path>) -> ResultStego<(Vec<i16>, AudioFileSpec)> {
+        let mut reader = hound::WavReader::open(path.as_ref())?;
+        let spec = reader.spec();
 
-            if header_bytes.len() == self.header().len() {
-                break;
+        let samples: Vec<i16> = match (spec.sample_format, spec.bits_per_sample) {
+            (hound::SampleFormat::Int, 16) => read_samples::<i16>(&mut reader)?,
+            (hound::SampleFormat::Int, 8) => read_samples::<i8>(&mut reader)?
+                .into_iter()
+                .map(|s| (s as i16) << 8)
+                .collect(),
+            (hound::SampleFormat::Int, bits @ (24 | 32)) => read_samples::<i32>(&mut reader)?
+                .into_iter()
+                .map(|s| (s >> (bits - 16)) as i16)
+                .collect(),
+            (hound::SampleFormat::Float, _) => read_samples::<f32>(&mut reader)?
+                .into_iter()
+                .map(|s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+                .collect(),
+            (_, bits) => {
+                return Err(StegoError::InvalidFile(format!(
+                    "Unsupported WAV format for normalization: {bits}-bit {:?}",
+                    spec.sample_format
+                )));
             }
-        }
+        };
 
-        if header_bytes == self.header().as_bytes() {
-            Ok(byte_iterator)
-        } else {
-            Err(StegoError::IncorrectPassword)
-        }
+        let normalized_spec = hound::WavSpec {
+            channels: spec.channels,
+            sample_rate: spec.sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+
+        Ok((samples, AudioFileSpec::Wav(normalized_spec)))
     }
 }
 
@@ -135,6 +385,8 @@ impl AudioSteganography<i16> for WAV16 {
     /// * `file_output` - Path where the output WAV file will be saved.
     /// * `message` - The message to hide.
     /// * `password` - The password used for steganography.
+    /// * `encrypt` - Whether to AEAD-seal the message with `password`, or embed it
+    ///   padded to the same envelope size with no ciphering.
     ///
     /// # Note
     ///
@@ -145,21 +397,43 @@ impl AudioSteganography<i16> for WAV16 {
         file_output: impl Into<PathBuf>,
         message: impl Into<String>,
         password: impl Into<String>,
+        encrypt: bool,
     ) -> ResultStego<()> {
         let input_path = file_input.into();
         let output_path = file_output.into();
 
-        self.validate_file(&input_path)?;
-        let mut reader = hound::WavReader::open(&input_path)?;
-        let mut samples = Self::read_sample(&mut reader)?;
+        let metadata = self
+            .preserve_metadata
+            .then(|| std::fs::read(&input_path))
+            .transpose()
+            .map_err(|err| StegoError::Other(err.to_string()))?
+            .map(|bytes| riff::read(&bytes));
+
+        let (mut samples, spec) = if self.normalize_input {
+            WAV16::read_and_normalize(&input_path)?
+        } else {
+            self.validate_file(&input_path)?;
+            let mut reader = hound::WavReader::open(&input_path)?;
+            let spec = AudioFileSpec::Wav(reader.spec());
+            (read_samples::<i16>(&mut reader)?, spec)
+        };
 
-        self.hide_message_binary(&mut samples, &message.into(), &password.into())?;
+        self.hide_message_binary(&mut samples, &message.into(), &password.into(), encrypt)?;
 
-        let mut writer = hound::WavWriter::create(output_path, reader.spec())?;
+        let mut writer = match spec {
+            AudioFileSpec::Wav(spec) => hound::WavWriter::create(&output_path, spec)?,
+        };
         for sample in samples {
             writer.write_sample(sample)?;
         }
         writer.finalize()?;
+
+        if let Some(metadata) = metadata {
+            let bytes = std::fs::read(&output_path).map_err(|err| StegoError::Other(err.to_string()))?;
+            let spliced = riff::splice(&bytes, &metadata);
+            std::fs::write(&output_path, spliced).map_err(|err| StegoError::Other(err.to_string()))?;
+        }
+
         Ok(())
     }
 
@@ -172,6 +446,8 @@ impl AudioSteganography<i16> for WAV16 {
     /// * `samples` - A mutable slice of audio samples.
     /// * `message` - The message to hide.
     /// * `password` - The password used for random index generation.
+    /// * `encrypt` - Whether to AEAD-seal the message with `password`, or embed it
+    ///   padded to the same envelope size with no ciphering.
     ///
     /// # Examples
     ///
@@ -180,7 +456,7 @@ impl AudioSteganography<i16> for WAV16 {
     /// # let wav16 = WAV16::builder().lsb_deep(1).settings(Settings::new("../sw_config.toml").unwrap()).build().unwrap();
     ///
     /// let mut samples = vec![8; 1_000];
-    /// wav16.hide_message_binary(&mut samples, "Test message", "_").unwrap();
+    /// wav16.hide_message_binary(&mut samples, "Test message", "_", false).unwrap();
     /// let res = wav16.extract_message_binary(&samples, "_").unwrap();
     /// assert_eq!(res, "Test message");
     /// ```
@@ -189,39 +465,56 @@ impl AudioSteganography<i16> for WAV16 {
         samples: &mut [i16],
         message: &str,
         password: &str,
+        encrypt: bool,
     ) -> ResultStego<()> {
-        let header_bytes = self.header().as_bytes();
-        let message_bytes = message.as_bytes();
+        let (header_bytes, envelope) =
+            self.prepare_envelope(message, password, encrypt, samples.len())?;
+        let mut indices_iter = self.indices(samples.len(), password);
 
-        let total_bytes = header_bytes.len() + message_bytes.len() + 1;
-        self.is_enough_samples(total_bytes, samples.len())?;
-
-        let mask = !self.get_mask();
-        let indices_iter = UniqueRandomIndices::new(samples.len(), password, self.max_occupancy());
-        let mut message = header_bytes
-            .iter()
-            .chain(message_bytes.iter())
-            .chain(iter::once(&0))
-            .flat_map(|&byte| (0..8).rev().map(move |shift| (byte >> shift) & 1));
-
-        let mut write_full = false;
-        'sample: for sample_index in indices_iter {
-            let mut value: u16 = 0;
-            for _ in 0..self.lsb_deep {
-                value = (value << 1)
-                    | (if let Some(bit) = message.next() {
-                        bit as u16
-                    } else {
-                        write_full = true;
-                        0u16
-                    });
-            }
+        write_bits(
+            samples,
+            &mut indices_iter,
+            HEADER_LSB_DEEP,
+            header_bytes.into_iter(),
+        );
+        write_bits(samples, &mut indices_iter, self.lsb_deep, envelope.into_iter());
+
+        Ok(())
+    }
+
+    /// Real per-chunk progress for the flagship format: wraps the same index
+    /// iterator [`hide_message_binary`](Self::hide_message_binary) uses in
+    /// [`ProgressIndices`], reporting every 4096 indices consumed instead of the
+    /// default trait method's single 0%/100% jump.
+    fn hide_message_binary_with_progress(
+        &self,
+        samples: &mut [i16],
+        message: &str,
+        password: &str,
+        encrypt: bool,
+        on_progress: &mut dyn FnMut(usize, usize),
+    ) -> ResultStego<()> {
+        const REPORT_EVERY: usize = 4096;
+
+        let (header_bytes, envelope) =
+            self.prepare_envelope(message, password, encrypt, samples.len())?;
+        let total = samples.len();
+        on_progress(0, total);
+
+        let indices_iter = self.indices(total, password);
+        let mut indices_iter = ProgressIndices::new(indices_iter, total, REPORT_EVERY, &mut *on_progress);
+
+        write_bits(
+            samples,
+            &mut indices_iter,
+            HEADER_LSB_DEEP,
+            header_bytes.into_iter(),
+        );
+        write_bits(samples, &mut indices_iter, self.lsb_deep, envelope.into_iter());
+        drop(indices_iter);
+
+        on_progress(total, total);
 
-            samples[sample_index] = (samples[sample_index] & mask) | (value as i16);
-            if write_full {
-                break 'sample;
-            }
-        }
         Ok(())
     }
 
@@ -244,7 +537,7 @@ impl AudioSteganography<i16> for WAV16 {
         self.validate_file(&input_path)?;
 
         let mut reader = hound::WavReader::open(&input_path)?;
-        let samples = Self::read_sample(&mut reader)?;
+        let samples = read_samples::<i16>(&mut reader)?;
 
         self.extract_message_binary(&samples, &password.into())
     }
@@ -269,25 +562,50 @@ impl AudioSteganography<i16> for WAV16 {
     /// # let wav16 = WAV16::builder().lsb_deep(1).settings(Settings::new("../sw_config.toml").unwrap()).build().unwrap();
     ///
     /// let mut samples = vec![8; 1_000];
-    /// wav16.hide_message_binary(&mut samples, "Test message", "_").unwrap();
+    /// wav16.hide_message_binary(&mut samples, "Test message", "_", false).unwrap();
     /// let res = wav16.extract_message_binary(&samples, "_").unwrap();
     /// assert_eq!(res, "Test message");
     /// ```
     fn extract_message_binary(&self, samples: &[i16], password: &str) -> ResultStego<String> {
-        let mut indices_iter =
-            UniqueRandomIndices::new(samples.len(), password, self.max_occupancy());
+        let mut indices_iter = self.indices(samples.len(), password);
 
-        let byte_iter = self.validate_header(samples, &mut indices_iter)?;
-        let mut result: Vec<u8> = Vec::new();
+        let header = read_header(
+            samples,
+            &mut indices_iter,
+            self.usable_sample_count(samples.len()),
+            self.max_occupancy(),
+            16,
+        )?;
 
-        for byte in byte_iter {
-            if byte == 0 {
-                return Ok(String::from_utf8(result).unwrap_or_default());
-            }
-            result.push(byte);
+        let mut payload_iter = ByteIterator::new(
+            samples,
+            &mut indices_iter,
+            i16::mask_for(header.lsb_deep),
+            header.lsb_deep,
+            0,
+            0,
+        );
+
+        let envelope: Vec<u8> = (&mut payload_iter)
+            .take(header.payload_len as usize)
+            .collect();
+        if envelope.len() != header.payload_len as usize {
+            return Err(StegoError::FailedToReceiveMessage);
+        }
+
+        let plaintext = crypto::decrypt(password, &envelope)?;
+        if plaintext.len() < CHECKSUM_LEN {
+            return Err(StegoError::IntegrityCheckFailed);
         }
+        let (checksum_bytes, frame) = plaintext.split_at(CHECKSUM_LEN);
 
-        Err(StegoError::FailedToReceiveMessage)
+        if checksum_bytes != checksum(frame) {
+            return Err(StegoError::IntegrityCheckFailed);
+        }
+
+        let message_bytes = compression::decompress(frame)?;
+
+        String::from_utf8(message_bytes).map_err(|_| StegoError::FailedToReceiveMessage)
     }
 
     /// Clears the secret message embedded in a WAV file using the given password.
@@ -311,8 +629,15 @@ impl AudioSteganography<i16> for WAV16 {
         let input_path = file.into();
         self.validate_file(&input_path)?;
 
+        let metadata = self
+            .preserve_metadata
+            .then(|| std::fs::read(&input_path))
+            .transpose()
+            .map_err(|err| StegoError::Other(err.to_string()))?
+            .map(|bytes| riff::read(&bytes));
+
         let mut reader = hound::WavReader::open(&input_path)?;
-        let mut samples = Self::read_sample(&mut reader)?;
+        let mut samples = read_samples::<i16>(&mut reader)?;
 
         self.clear_secret_message_binary(&mut samples, password)?;
 
@@ -322,6 +647,12 @@ impl AudioSteganography<i16> for WAV16 {
         }
         writer.finalize()?;
 
+        if let Some(metadata) = metadata {
+            let bytes = std::fs::read(&input_path).map_err(|err| StegoError::Other(err.to_string()))?;
+            let spliced = riff::splice(&bytes, &metadata);
+            std::fs::write(&input_path, spliced).map_err(|err| StegoError::Other(err.to_string()))?;
+        }
+
         Ok(())
     }
 
@@ -345,32 +676,29 @@ impl AudioSteganography<i16> for WAV16 {
     /// let _ = wav16.clear_secret_message_binary(&mut samples, "my_password");
     /// ```
     fn clear_secret_message_binary(&self, samples: &mut [i16], password: &str) -> ResultStego<()> {
-        let indices_iter = UniqueRandomIndices::new(samples.len(), password, self.max_occupancy());
-        let mask = self.get_mask();
-
-        self.validate_header(samples, &mut indices_iter.clone())?;
-
-        let (mut current_byte, mut bit_count) = (0_u8, 0_u8);
-        for sample_index in indices_iter {
-            let encoded = (samples[sample_index] & mask) as u16;
-            samples[sample_index] &= !mask;
-
-            for shift in (0..self.lsb_deep).rev() {
-                let bit = ((encoded >> shift) & 1) as u8;
-                current_byte = (current_byte << 1) | bit;
-                bit_count += 1;
+        let mut indices_iter = self.indices(samples.len(), password);
+
+        let header = {
+            let mut header_probe = indices_iter.clone();
+            read_header(
+                samples,
+                &mut header_probe,
+                self.usable_sample_count(samples.len()),
+                self.max_occupancy(),
+                16,
+            )?
+        };
+        let header_len = header.encode().len();
 
-                if bit_count == 8 {
-                    if current_byte == 0 {
-                        return Ok(());
-                    }
+        clear_bits(samples, &mut indices_iter, HEADER_LSB_DEEP, header_len);
+        clear_bits(
+            samples,
+            &mut indices_iter,
+            header.lsb_deep,
+            header.payload_len as usize,
+        );
 
-                    current_byte = 0;
-                    bit_count = 0;
-                }
-            }
-        }
-        Err(StegoError::FailedToReceiveMessage)
+        Ok(())
     }
 
     /// Validates that the provided WAV file is valid.
@@ -407,7 +735,7 @@ impl AudioSteganography<i16> for WAV16 {
             .map_err(|_| StegoError::Other("Error reading WAV".to_string()))?;
 
         let spec = reader.spec();
-        let samples = WAV16::read_sample(&mut reader)
+        let samples = read_samples::<i16>(&mut reader)
             .map_err(|_| StegoError::Other("Error reading samples".to_string()))?;
 
         Ok((samples, AudioFileSpec::Wav(spec)))
@@ -435,6 +763,680 @@ impl AudioSteganography<i16> for WAV16 {
     fn default_filename(&self) -> String {
         "wav_16.wav".to_string()
     }
+
+    fn format_id(&self) -> u8 {
+        WAV16_FORMAT_ID
+    }
+
+    fn capacity_bytes(&self, sample_len: usize) -> usize {
+        let sample_len = self.usable_sample_count(sample_len);
+        let usable_bits = sample_len * self.max_occupancy() / 100 * self.lsb_deep as usize;
+        (usable_bits / 8).saturating_sub(header_and_crypto_overhead())
+    }
+
+    fn overhead_bytes(&self) -> usize {
+        header_and_crypto_overhead()
+    }
+}
+
+/// Defines a steganography encoder/decoder for an integer-PCM WAV bit depth that
+/// isn't 16-bit, reusing the shared [`StegoSample`]-generic helpers above for every
+/// bit-twiddling detail so each width only spells out what's actually different:
+/// its sample type, format id, bit depth, and default filename/error text.
+macro_rules! integer_wav_format {
+    (
+        $(#[$doc:meta])*
+        $name:ident, $builder:ident, $builder_name:literal, $sample:ty, $format_id:expr,
+        $bits_per_sample:expr, $default_filename:expr, $invalid_file_message:expr
+    ) => {
+        $(#[$doc])*
+        #[derive(Builder, Debug, PartialEq)]
+        #[builder(build_fn(validate = "Self::validate"))]
+        #[builder(name = $builder_name)]
+        pub struct $name {
+            lsb_deep: u8,
+            #[builder(default)]
+            settings: Settings,
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                let setting = Settings::default();
+                $name::builder()
+                    .lsb_deep(setting.stego_wave_lib.default_lsb_deep)
+                    .settings(setting)
+                    .build()
+                    .unwrap()
+            }
+        }
+
+        impl $builder {
+            fn validate(&self) -> Result<(), String> {
+                if let Some(ref lsb_deep) = self.lsb_deep {
+                    match *lsb_deep {
+                        ld if ld == 0 || ld > $bits_per_sample => Err(format!(
+                            "lsb_deep must be between 1 and {}",
+                            $bits_per_sample
+                        )),
+                        _ => Ok(()),
+                    }
+                } else {
+                    Ok(())
+                }
+            }
+        }
+
+        impl $name {
+            fn max_occupancy(&self) -> usize {
+                self.settings.stego_wave_lib.max_occupancy
+            }
+        }
+
+        impl AudioSteganography<$sample> for $name {
+            type Builder = $builder;
+
+            fn builder() -> Self::Builder {
+                $builder::default()
+            }
+
+            fn hide_message(
+                &self,
+                file_input: impl Into<PathBuf>,
+                file_output: impl Into<PathBuf>,
+                message: impl Into<String>,
+                password: impl Into<String>,
+                encrypt: bool,
+            ) -> ResultStego<()> {
+                let input_path = file_input.into();
+                let output_path = file_output.into();
+
+                self.validate_file(&input_path)?;
+                let mut reader = hound::WavReader::open(&input_path)?;
+                let mut samples = read_samples::<$sample>(&mut reader)?;
+
+                self.hide_message_binary(&mut samples, &message.into(), &password.into(), encrypt)?;
+
+                let mut writer = hound::WavWriter::create(output_path, reader.spec())?;
+                for sample in samples {
+                    writer.write_sample(sample)?;
+                }
+                writer.finalize()?;
+                Ok(())
+            }
+
+            fn hide_message_binary(
+                &self,
+                samples: &mut [$sample],
+                message: &str,
+                password: &str,
+                encrypt: bool,
+            ) -> ResultStego<()> {
+                let message_bytes = message.as_bytes();
+                let mut plaintext = Vec::with_capacity(CHECKSUM_LEN + message_bytes.len());
+                plaintext.extend_from_slice(&checksum(message_bytes));
+                plaintext.extend_from_slice(message_bytes);
+
+                let envelope = crypto::encrypt(password, &plaintext, encrypt)?;
+                let header_bytes = PayloadHeader {
+                    lsb_deep: self.lsb_deep,
+                    format_id: self.format_id(),
+                    payload_len: envelope.len() as u64,
+                }
+                .encode();
+
+                check_capacity(
+                    header_bytes.len(),
+                    envelope.len(),
+                    samples.len(),
+                    self.lsb_deep,
+                    self.max_occupancy(),
+                )?;
+
+                let mut indices_iter =
+                    UniqueRandomIndices::new(samples.len(), password, self.max_occupancy());
+
+                write_bits(
+                    samples,
+                    &mut indices_iter,
+                    HEADER_LSB_DEEP,
+                    header_bytes.into_iter(),
+                );
+                write_bits(samples, &mut indices_iter, self.lsb_deep, envelope.into_iter());
+
+                Ok(())
+            }
+
+            fn extract_message(
+                &self,
+                file: impl Into<PathBuf>,
+                password: impl Into<String>,
+            ) -> ResultStego<String> {
+                let input_path = file.into();
+                self.validate_file(&input_path)?;
+
+                let mut reader = hound::WavReader::open(&input_path)?;
+                let samples = read_samples::<$sample>(&mut reader)?;
+
+                self.extract_message_binary(&samples, &password.into())
+            }
+
+            fn extract_message_binary(
+                &self,
+                samples: &[$sample],
+                password: &str,
+            ) -> ResultStego<String> {
+                let mut indices_iter =
+                    UniqueRandomIndices::new(samples.len(), password, self.max_occupancy());
+
+                let header = read_header(
+                    samples,
+                    &mut indices_iter,
+                    samples.len(),
+                    self.max_occupancy(),
+                    $bits_per_sample,
+                )?;
+
+                let mut payload_iter = ByteIterator::new(
+                    samples,
+                    &mut indices_iter,
+                    <$sample>::mask_for(header.lsb_deep),
+                    header.lsb_deep,
+                    0,
+                    0,
+                );
+
+                let envelope: Vec<u8> = (&mut payload_iter)
+                    .take(header.payload_len as usize)
+                    .collect();
+                if envelope.len() != header.payload_len as usize {
+                    return Err(StegoError::FailedToReceiveMessage);
+                }
+
+                let plaintext = crypto::decrypt(password, &envelope)?;
+                if plaintext.len() < CHECKSUM_LEN {
+                    return Err(StegoError::IntegrityCheckFailed);
+                }
+                let (checksum_bytes, message_bytes) = plaintext.split_at(CHECKSUM_LEN);
+
+                if checksum_bytes != checksum(message_bytes) {
+                    return Err(StegoError::IntegrityCheckFailed);
+                }
+
+                String::from_utf8(message_bytes.to_vec())
+                    .map_err(|_| StegoError::FailedToReceiveMessage)
+            }
+
+            fn clear_secret_message(
+                &self,
+                file: impl Into<PathBuf>,
+                password: &str,
+            ) -> ResultStego<()> {
+                let input_path = file.into();
+                self.validate_file(&input_path)?;
+
+                let mut reader = hound::WavReader::open(&input_path)?;
+                let mut samples = read_samples::<$sample>(&mut reader)?;
+
+                self.clear_secret_message_binary(&mut samples, password)?;
+
+                let mut writer = hound::WavWriter::create(&input_path, reader.spec())?;
+                for sample in samples {
+                    writer.write_sample(sample)?;
+                }
+                writer.finalize()?;
+
+                Ok(())
+            }
+
+            fn clear_secret_message_binary(
+                &self,
+                samples: &mut [$sample],
+                password: &str,
+            ) -> ResultStego<()> {
+                let mut indices_iter =
+                    UniqueRandomIndices::new(samples.len(), password, self.max_occupancy());
+
+                let header = {
+                    let mut header_probe = indices_iter.clone();
+                    read_header(
+                        samples,
+                        &mut header_probe,
+                        samples.len(),
+                        self.max_occupancy(),
+                        $bits_per_sample,
+                    )?
+                };
+                let header_len = header.encode().len();
+
+                clear_bits(samples, &mut indices_iter, HEADER_LSB_DEEP, header_len);
+                clear_bits(
+                    samples,
+                    &mut indices_iter,
+                    header.lsb_deep,
+                    header.payload_len as usize,
+                );
+
+                Ok(())
+            }
+
+            fn validate_file(&self, file: &Path) -> ResultStego<()> {
+                let reader = hound::WavReader::open(file)?;
+                if reader.spec().bits_per_sample != $bits_per_sample {
+                    return Err(StegoError::InvalidFile($invalid_file_message.to_string()));
+                }
+                Ok(())
+            }
+
+            fn read_samples_from_byte(
+                &self,
+                byte: Vec<u8>,
+            ) -> ResultStego<(Vec<$sample>, AudioFileSpec)> {
+                let cursor = Cursor::new(byte);
+                let mut reader = hound::WavReader::new(cursor)
+                    .map_err(|_| StegoError::Other("Error reading WAV".to_string()))?;
+
+                let spec = reader.spec();
+                let samples = read_samples::<$sample>(&mut reader)
+                    .map_err(|_| StegoError::Other("Error reading samples".to_string()))?;
+
+                Ok((samples, AudioFileSpec::Wav(spec)))
+            }
+
+            fn write_samples_to_byte(
+                &self,
+                spec: AudioFileSpec,
+                samples: &[$sample],
+            ) -> ResultStego<Vec<u8>> {
+                let mut out_buf = Cursor::new(Vec::<u8>::new());
+                let mut writer = match spec {
+                    AudioFileSpec::Wav(spec) => hound::WavWriter::new(&mut out_buf, spec)?,
+                };
+
+                for sample in samples {
+                    writer
+                        .write_sample(*sample)
+                        .map_err(|_| StegoError::Other("Error writing sample".to_string()))?;
+                }
+
+                writer
+                    .finalize()
+                    .map_err(|_| StegoError::Other("Error finalizing writer".to_string()))?;
+
+                Ok(out_buf.into_inner())
+            }
+
+            fn default_filename(&self) -> String {
+                $default_filename.to_string()
+            }
+
+            fn format_id(&self) -> u8 {
+                $format_id
+            }
+
+            fn capacity_bytes(&self, sample_len: usize) -> usize {
+                let usable_bits = sample_len * self.max_occupancy() / 100 * self.lsb_deep as usize;
+                (usable_bits / 8).saturating_sub(header_and_crypto_overhead())
+            }
+
+            fn overhead_bytes(&self) -> usize {
+                header_and_crypto_overhead()
+            }
+        }
+    };
+}
+
+integer_wav_format!(
+    /// A steganography encoder/decoder for 8-bit WAV audio files, sharing its
+    /// embedding logic with [`WAV16`]/[`WAV24`]/[`WAV32`] via [`StegoSample`].
+    WAV8,
+    WAV8Builder,
+    "WAV8Builder",
+    i8,
+    WAV8_FORMAT_ID,
+    8,
+    "wav_8.wav",
+    "Only 8-bit WAV file supported"
+);
+
+integer_wav_format!(
+    /// A steganography encoder/decoder for 24-bit WAV audio files. `hound` widens
+    /// 24-bit samples to `i32` on read/write, so `lsb_deep` is capped at 24 rather
+    /// than 32 to stay within the bits that actually round-trip to the carrier.
+    WAV24,
+    WAV24Builder,
+    "WAV24Builder",
+    i32,
+    WAV24_FORMAT_ID,
+    24,
+    "wav_24.wav",
+    "Only 24-bit WAV file supported"
+);
+
+integer_wav_format!(
+    /// A steganography encoder/decoder for 32-bit WAV audio files, sharing its
+    /// embedding logic with [`WAV16`]/[`WAV8`]/[`WAV24`] via [`StegoSample`].
+    WAV32,
+    WAV32Builder,
+    "WAV32Builder",
+    i32,
+    WAV32_FORMAT_ID,
+    32,
+    "wav_32.wav",
+    "Only 32-bit WAV file supported"
+);
+
+/// A steganography encoder/decoder for 32-bit IEEE-float WAV audio files (the
+/// format most DAWs and audio editors export).
+///
+/// Embeds into the low bits of each sample's mantissa via [`StegoSample`]'s `f32`
+/// impl, and skips any NaN/±Infinity sample as a carrier via [`FiniteIndices`] so
+/// embedding never perturbs, or depends on the bits of, a non-finite sample.
+/// `lsb_deep` is capped at [`MAX_LSB_DEEP_F32`] to keep the perturbation inaudible.
+#[derive(Builder, Debug, PartialEq)]
+#[builder(build_fn(validate = "Self::validate"))]
+#[builder(name = "WAV32FBuilder")]
+pub struct WAV32F {
+    lsb_deep: u8,
+    #[builder(default)]
+    settings: Settings,
+}
+
+impl Default for WAV32F {
+    fn default() -> Self {
+        let setting = Settings::default();
+        WAV32F::builder()
+            .lsb_deep(setting.stego_wave_lib.default_lsb_deep.min(MAX_LSB_DEEP_F32))
+            .settings(setting)
+            .build()
+            .unwrap()
+    }
+}
+
+impl WAV32FBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(ref lsb_deep) = self.lsb_deep {
+            match *lsb_deep {
+                ld if ld == 0 || ld > MAX_LSB_DEEP_F32 => Err(format!(
+                    "lsb_deep must be between 1 and {MAX_LSB_DEEP_F32}"
+                )),
+                _ => Ok(()),
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl WAV32F {
+    fn max_occupancy(&self) -> usize {
+        self.settings.stego_wave_lib.max_occupancy
+    }
+}
+
+impl AudioSteganography<f32> for WAV32F {
+    type Builder = WAV32FBuilder;
+
+    fn builder() -> Self::Builder {
+        WAV32FBuilder::default()
+    }
+
+    fn hide_message(
+        &self,
+        file_input: impl Into<PathBuf>,
+        file_output: impl Into<PathBuf>,
+        message: impl Into<String>,
+        password: impl Into<String>,
+        encrypt: bool,
+    ) -> ResultStego<()> {
+        let input_path = file_input.into();
+        let output_path = file_output.into();
+
+        self.validate_file(&input_path)?;
+        let mut reader = hound::WavReader::open(&input_path)?;
+        let mut samples = read_samples::<f32>(&mut reader)?;
+
+        self.hide_message_binary(&mut samples, &message.into(), &password.into(), encrypt)?;
+
+        let mut writer = hound::WavWriter::create(output_path, reader.spec())?;
+        for sample in samples {
+            writer.write_sample(sample)?;
+        }
+        writer.finalize()?;
+        Ok(())
+    }
+
+    fn hide_message_binary(
+        &self,
+        samples: &mut [f32],
+        message: &str,
+        password: &str,
+        encrypt: bool,
+    ) -> ResultStego<()> {
+        let message_bytes = message.as_bytes();
+        let mut plaintext = Vec::with_capacity(CHECKSUM_LEN + message_bytes.len());
+        plaintext.extend_from_slice(&checksum(message_bytes));
+        plaintext.extend_from_slice(message_bytes);
+
+        let envelope = crypto::encrypt(password, &plaintext, encrypt)?;
+        let header_bytes = PayloadHeader {
+            lsb_deep: self.lsb_deep,
+            format_id: self.format_id(),
+            payload_len: envelope.len() as u64,
+        }
+        .encode();
+
+        check_capacity(
+            header_bytes.len(),
+            envelope.len(),
+            samples.len(),
+            self.lsb_deep,
+            self.max_occupancy(),
+        )?;
+
+        let finite = finite_mask(samples);
+        let mut indices_iter =
+            UniqueRandomIndices::new(samples.len(), password, self.max_occupancy());
+
+        write_bits(
+            samples,
+            &mut FiniteIndices {
+                finite: &finite,
+                inner: &mut indices_iter,
+            },
+            HEADER_LSB_DEEP,
+            header_bytes.into_iter(),
+        );
+        write_bits(
+            samples,
+            &mut FiniteIndices {
+                finite: &finite,
+                inner: &mut indices_iter,
+            },
+            self.lsb_deep,
+            envelope.into_iter(),
+        );
+
+        Ok(())
+    }
+
+    fn extract_message(
+        &self,
+        file: impl Into<PathBuf>,
+        password: impl Into<String>,
+    ) -> ResultStego<String> {
+        let input_path = file.into();
+        self.validate_file(&input_path)?;
+
+        let mut reader = hound::WavReader::open(&input_path)?;
+        let samples = read_samples::<f32>(&mut reader)?;
+
+        self.extract_message_binary(&samples, &password.into())
+    }
+
+    fn extract_message_binary(&self, samples: &[f32], password: &str) -> ResultStego<String> {
+        let finite = finite_mask(samples);
+        let mut indices_iter =
+            UniqueRandomIndices::new(samples.len(), password, self.max_occupancy());
+        let mut indices = FiniteIndices {
+            finite: &finite,
+            inner: &mut indices_iter,
+        };
+
+        let header = read_header(
+            samples,
+            &mut indices,
+            samples.len(),
+            self.max_occupancy(),
+            MAX_LSB_DEEP_F32,
+        )?;
+
+        let mut payload_iter = ByteIterator::new(
+            samples,
+            &mut indices,
+            f32::mask_for(header.lsb_deep),
+            header.lsb_deep,
+            0,
+            0,
+        );
+
+        let envelope: Vec<u8> = (&mut payload_iter)
+            .take(header.payload_len as usize)
+            .collect();
+        if envelope.len() != header.payload_len as usize {
+            return Err(StegoError::FailedToReceiveMessage);
+        }
+
+        let plaintext = crypto::decrypt(password, &envelope)?;
+        if plaintext.len() < CHECKSUM_LEN {
+            return Err(StegoError::IntegrityCheckFailed);
+        }
+        let (checksum_bytes, message_bytes) = plaintext.split_at(CHECKSUM_LEN);
+
+        if checksum_bytes != checksum(message_bytes) {
+            return Err(StegoError::IntegrityCheckFailed);
+        }
+
+        String::from_utf8(message_bytes.to_vec()).map_err(|_| StegoError::FailedToReceiveMessage)
+    }
+
+    fn clear_secret_message(&self, file: impl Into<PathBuf>, password: &str) -> ResultStego<()> {
+        let input_path = file.into();
+        self.validate_file(&input_path)?;
+
+        let mut reader = hound::WavReader::open(&input_path)?;
+        let mut samples = read_samples::<f32>(&mut reader)?;
+
+        self.clear_secret_message_binary(&mut samples, password)?;
+
+        let mut writer = hound::WavWriter::create(&input_path, reader.spec())?;
+        for sample in samples {
+            writer.write_sample(sample)?;
+        }
+        writer.finalize()?;
+
+        Ok(())
+    }
+
+    fn clear_secret_message_binary(&self, samples: &mut [f32], password: &str) -> ResultStego<()> {
+        let finite = finite_mask(samples);
+        let mut indices_iter =
+            UniqueRandomIndices::new(samples.len(), password, self.max_occupancy());
+
+        let header = {
+            let mut header_probe = FiniteIndices {
+                finite: &finite,
+                inner: indices_iter.clone(),
+            };
+            read_header(
+                samples,
+                &mut header_probe,
+                samples.len(),
+                self.max_occupancy(),
+                MAX_LSB_DEEP_F32,
+            )?
+        };
+        let header_len = header.encode().len();
+
+        clear_bits(
+            samples,
+            &mut FiniteIndices {
+                finite: &finite,
+                inner: &mut indices_iter,
+            },
+            HEADER_LSB_DEEP,
+            header_len,
+        );
+        clear_bits(
+            samples,
+            &mut FiniteIndices {
+                finite: &finite,
+                inner: &mut indices_iter,
+            },
+            header.lsb_deep,
+            header.payload_len as usize,
+        );
+
+        Ok(())
+    }
+
+    fn validate_file(&self, file: &Path) -> ResultStego<()> {
+        let reader = hound::WavReader::open(file)?;
+        let spec = reader.spec();
+        if spec.bits_per_sample != 32 || spec.sample_format != hound::SampleFormat::Float {
+            return Err(StegoError::InvalidFile(
+                "Only 32-bit float WAV file supported".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn read_samples_from_byte(&self, byte: Vec<u8>) -> ResultStego<(Vec<f32>, AudioFileSpec)> {
+        let cursor = Cursor::new(byte);
+        let mut reader = hound::WavReader::new(cursor)
+            .map_err(|_| StegoError::Other("Error reading WAV".to_string()))?;
+
+        let spec = reader.spec();
+        let samples = read_samples::<f32>(&mut reader)
+            .map_err(|_| StegoError::Other("Error reading samples".to_string()))?;
+
+        Ok((samples, AudioFileSpec::Wav(spec)))
+    }
+
+    fn write_samples_to_byte(&self, spec: AudioFileSpec, samples: &[f32]) -> ResultStego<Vec<u8>> {
+        let mut out_buf = Cursor::new(Vec::<u8>::new());
+        let mut writer = match spec {
+            AudioFileSpec::Wav(spec) => hound::WavWriter::new(&mut out_buf, spec)?,
+        };
+
+        for sample in samples {
+            writer
+                .write_sample(*sample)
+                .map_err(|_| StegoError::Other("Error writing sample".to_string()))?;
+        }
+
+        writer
+            .finalize()
+            .map_err(|_| StegoError::Other("Error finalizing writer".to_string()))?;
+
+        Ok(out_buf.into_inner())
+    }
+
+    fn default_filename(&self) -> String {
+        "wav_32f.wav".to_string()
+    }
+
+    fn format_id(&self) -> u8 {
+        WAV32F_FORMAT_ID
+    }
+
+    fn capacity_bytes(&self, sample_len: usize) -> usize {
+        let usable_bits = sample_len * self.max_occupancy() / 100 * self.lsb_deep as usize;
+        (usable_bits / 8).saturating_sub(header_and_crypto_overhead())
+    }
+
+    fn overhead_bytes(&self) -> usize {
+        header_and_crypto_overhead()
+    }
 }
 
 #[cfg(test)]
@@ -455,7 +1457,7 @@ mod tests {
                 .settings(Settings::new(CONFIG_FILE).unwrap())
                 .build()?;
 
-            wav16.hide_message_binary(sample, &format!("{i} test {i}"), "_")?;
+            wav16.hide_message_binary(sample, &format!("{i} test {i}"), "_", true)?;
             let res = wav16.extract_message_binary(sample, "_")?;
             assert_eq!(res, format!("{i} test {i}"));
         }
@@ -471,7 +1473,7 @@ mod tests {
             .settings(Settings::new(CONFIG_FILE).unwrap())
             .build()?;
 
-        wav16.hide_message_binary(sample, "test", "qwerty1")?;
+        wav16.hide_message_binary(sample, "test", "qwerty1", true)?;
 
         assert!(wav16.extract_message_binary(sample, "qwerty2").is_err());
         assert!(wav16.extract_message_binary(sample, "qwerty").is_err());
@@ -523,7 +1525,7 @@ mod tests {
         let message = "Hello World!";
         let password = "qwerty1234";
 
-        wav16.hide_message(&input_path, &output_path, message, password)?;
+        wav16.hide_message(&input_path, &output_path, message, password, true)?;
         let res = wav16.extract_message(&output_path, password)?;
 
         assert_eq!(res, message);
@@ -550,7 +1552,7 @@ mod tests {
         let message = "Hello World!";
         let password = "qwerty1234";
 
-        wav16.hide_message(&input_path, &output_path, message, password)?;
+        wav16.hide_message(&input_path, &output_path, message, password, true)?;
         let res = wav16.extract_message(&output_path, "wrong_password");
 
         match res {
@@ -579,7 +1581,7 @@ mod tests {
         let message = "Hello World!";
         let password = "qwerty1234";
 
-        wav16.hide_message(&input_path, &output_path, message, password)?;
+        wav16.hide_message(&input_path, &output_path, message, password, true)?;
         let res = wav16.extract_message(&output_path, password)?;
         assert_eq!(res, message);
 
@@ -607,7 +1609,7 @@ mod tests {
             .lsb_deep(1)
             .settings(Settings::new(CONFIG_FILE).unwrap())
             .build()?
-            .hide_message(&input_path, &output_path, "test", "rest");
+            .hide_message(&input_path, &output_path, "test", "rest", true);
 
         match res {
             Err(StegoError::InvalidFile(err)) => assert_eq!(err, "Only 16-bit WAV file supported"),
@@ -619,4 +1621,200 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_preserve_metadata_round_trips_info_tags() -> Result<(), Box<dyn Error>> {
+        let samples: Vec<i16> = vec![0; 10_000];
+        let input_path = temp_path("input_preserve_metadata.wav");
+        let output_path = temp_path("output_preserve_metadata.wav");
+        create_wav_file(&input_path, 16, &samples)?;
+
+        let tags = vec![
+            ("IART".to_string(), "Test Artist".to_string()),
+            ("INAM".to_string(), "Test Title".to_string()),
+        ];
+        let original = std::fs::read(&input_path)?;
+        let spliced = crate::riff::splice(
+            &original,
+            &crate::riff::RiffMetadata {
+                info_tags: tags.clone(),
+                fact_chunk: None,
+            },
+        );
+        std::fs::write(&input_path, spliced)?;
+
+        let wav16 = WAV16::builder()
+            .lsb_deep(1)
+            .settings(Settings::new(CONFIG_FILE).unwrap())
+            .preserve_metadata(true)
+            .build()?;
+
+        let message = "Hello World!";
+        let password = "qwerty1234";
+
+        wav16.hide_message(&input_path, &output_path, message, password, true)?;
+        let res = wav16.extract_message(&output_path, password)?;
+        assert_eq!(res, message);
+
+        let output_bytes = std::fs::read(&output_path)?;
+        let metadata = crate::riff::read(&output_bytes);
+        assert_eq!(metadata.info_tags, tags);
+
+        let _ = fs::remove_file(input_path);
+        let _ = fs::remove_file(output_path);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_input_accepts_non_16_bit_cover() -> Result<(), Box<dyn Error>> {
+        let samples: Vec<i16> = vec![0; 10_000];
+        let input_path = temp_path("input_normalize.wav");
+        let output_path = temp_path("output_normalize.wav");
+        create_wav_file(&input_path, 8, &samples)?;
+
+        let wav16 = WAV16::builder()
+            .lsb_deep(1)
+            .settings(Settings::new(CONFIG_FILE).unwrap())
+            .normalize_input(true)
+            .build()?;
+
+        let message = "Hello World!";
+        let password = "qwerty1234";
+
+        wav16.hide_message(&input_path, &output_path, message, password, true)?;
+        let res = wav16.extract_message(&output_path, password)?;
+        assert_eq!(res, message);
+
+        let _ = fs::remove_file(input_path);
+        let _ = fs::remove_file(output_path);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_embed_channel_single_leaves_other_channel_untouched() -> Result<(), Box<dyn Error>> {
+        let channels = 2;
+        let original: Vec<i16> = vec![1234; 2_000];
+        let mut sample = original.clone();
+
+        let wav16 = WAV16::builder()
+            .lsb_deep(4)
+            .settings(Settings::new(CONFIG_FILE).unwrap())
+            .embed_channel(EmbedChannel::Single(0))
+            .channels(channels)
+            .build()?;
+
+        wav16.hide_message_binary(&mut sample, "Channel 0 only", "_", true)?;
+        let res = wav16.extract_message_binary(&sample, "_")?;
+        assert_eq!(res, "Channel 0 only");
+
+        for (i, (before, after)) in original.iter().zip(sample.iter()).enumerate() {
+            if i % channels as usize == 1 {
+                assert_eq!(before, after, "channel 1 sample at index {i} was modified");
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_embed_channel_round_robin_hide_and_extract() -> Result<(), Box<dyn Error>> {
+        let sample: &mut [i16; 2_000] = &mut [1234; 2_000];
+        let wav16 = WAV16::builder()
+            .lsb_deep(4)
+            .settings(Settings::new(CONFIG_FILE).unwrap())
+            .embed_channel(EmbedChannel::RoundRobin)
+            .channels(2)
+            .build()?;
+
+        wav16.hide_message_binary(sample, "Round robin", "_", true)?;
+        let res = wav16.extract_message_binary(sample, "_")?;
+        assert_eq!(res, "Round robin");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wav8_hide_and_extract_message() -> Result<(), Box<dyn Error>> {
+        let sample: &mut [i8; 1_000] = &mut [8; 1_000];
+        let wav8 = WAV8::builder()
+            .lsb_deep(4)
+            .settings(Settings::new(CONFIG_FILE).unwrap())
+            .build()?;
+
+        wav8.hide_message_binary(sample, "Hello, 8-bit!", "_", true)?;
+        let res = wav8.extract_message_binary(sample, "_")?;
+        assert_eq!(res, "Hello, 8-bit!");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wav24_hide_and_extract_message() -> Result<(), Box<dyn Error>> {
+        let sample: &mut [i32; 1_000] = &mut [8; 1_000];
+        let wav24 = WAV24::builder()
+            .lsb_deep(8)
+            .settings(Settings::new(CONFIG_FILE).unwrap())
+            .build()?;
+
+        wav24.hide_message_binary(sample, "Hello, 24-bit!", "_", true)?;
+        let res = wav24.extract_message_binary(sample, "_")?;
+        assert_eq!(res, "Hello, 24-bit!");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wav32_hide_and_extract_message() -> Result<(), Box<dyn Error>> {
+        let sample: &mut [i32; 1_000] = &mut [8; 1_000];
+        let wav32 = WAV32::builder()
+            .lsb_deep(8)
+            .settings(Settings::new(CONFIG_FILE).unwrap())
+            .build()?;
+
+        wav32.hide_message_binary(sample, "Hello, 32-bit!", "_", true)?;
+        let res = wav32.extract_message_binary(sample, "_")?;
+        assert_eq!(res, "Hello, 32-bit!");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wav32f_hide_and_extract_message() -> Result<(), Box<dyn Error>> {
+        let sample: &mut [f32; 1_000] = &mut [0.5; 1_000];
+        let wav32f = WAV32F::builder()
+            .lsb_deep(4)
+            .settings(Settings::new(CONFIG_FILE).unwrap())
+            .build()?;
+
+        wav32f.hide_message_binary(sample, "Hello, float!", "_", true)?;
+        let res = wav32f.extract_message_binary(sample, "_")?;
+        assert_eq!(res, "Hello, float!");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wav32f_skips_non_finite_samples() -> Result<(), Box<dyn Error>> {
+        let mut sample: Vec<f32> = vec![0.5; 1_000];
+        sample[10] = f32::NAN;
+        sample[20] = f32::INFINITY;
+        sample[30] = f32::NEG_INFINITY;
+
+        let wav32f = WAV32F::builder()
+            .lsb_deep(4)
+            .settings(Settings::new(CONFIG_FILE).unwrap())
+            .build()?;
+
+        wav32f.hide_message_binary(&mut sample, "Hello, float!", "_", true)?;
+        assert!(sample[10].is_nan());
+        assert_eq!(sample[20], f32::INFINITY);
+        assert_eq!(sample[30], f32::NEG_INFINITY);
+
+        let res = wav32f.extract_message_binary(&sample, "_")?;
+        assert_eq!(res, "Hello, float!");
+
+        Ok(())
+    }
 }
@@ -0,0 +1,114 @@
+/// A sorted set of half-open `[start, end)` ranges that merges adjacent or
+/// overlapping ranges on insert.
+///
+/// Used to track which byte ranges of a file streamed in over multiple chunks
+/// have arrived so far, regardless of the order they arrive in.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: Vec<(usize, usize)>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts `[start, end)`, coalescing it with any range it touches or overlaps.
+    pub fn insert(&mut self, start: usize, end: usize) {
+        if start >= end {
+            return;
+        }
+
+        let mut merged_start = start;
+        let mut merged_end = end;
+        let mut retained = Vec::with_capacity(self.ranges.len() + 1);
+
+        for &(s, e) in &self.ranges {
+            if e < merged_start || s > merged_end {
+                retained.push((s, e));
+            } else {
+                merged_start = merged_start.min(s);
+                merged_end = merged_end.max(e);
+            }
+        }
+
+        let pos = retained.partition_point(|&(s, _)| s < merged_start);
+        retained.insert(pos, (merged_start, merged_end));
+        self.ranges = retained;
+    }
+
+    /// Length of the contiguous run starting at byte `0` that has fully arrived.
+    pub fn contiguous_prefix_len(&self) -> usize {
+        match self.ranges.first() {
+            Some(&(0, end)) => end,
+            _ => 0,
+        }
+    }
+
+    /// Whether `[start, end)` shares any bytes with a range already in this set,
+    /// e.g. a chunk resent after a connection drop was misjudged and actually
+    /// duplicates bytes the set already has.
+    pub fn overlaps(&self, start: usize, end: usize) -> bool {
+        if start >= end {
+            return false;
+        }
+
+        self.ranges.iter().any(|&(s, e)| start < e && s < end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntervalSet;
+
+    #[test]
+    fn merges_adjacent_ranges() {
+        let mut set = IntervalSet::new();
+        set.insert(0, 10);
+        set.insert(10, 20);
+
+        assert_eq!(set.contiguous_prefix_len(), 20);
+    }
+
+    #[test]
+    fn merges_overlapping_ranges() {
+        let mut set = IntervalSet::new();
+        set.insert(5, 15);
+        set.insert(10, 25);
+        assert_eq!(set.contiguous_prefix_len(), 0);
+
+        set.insert(0, 6);
+        assert_eq!(set.contiguous_prefix_len(), 25);
+    }
+
+    #[test]
+    fn out_of_order_chunks_still_coalesce() {
+        let mut set = IntervalSet::new();
+        set.insert(20, 30);
+        set.insert(0, 10);
+        set.insert(10, 20);
+
+        assert_eq!(set.contiguous_prefix_len(), 30);
+    }
+
+    #[test]
+    fn gap_blocks_contiguous_prefix() {
+        let mut set = IntervalSet::new();
+        set.insert(0, 10);
+        set.insert(20, 30);
+
+        assert_eq!(set.contiguous_prefix_len(), 10);
+    }
+
+    #[test]
+    fn overlap_is_detected_regardless_of_arrival_order() {
+        let mut set = IntervalSet::new();
+        set.insert(10, 20);
+
+        assert!(set.overlaps(15, 25));
+        assert!(set.overlaps(5, 15));
+        assert!(set.overlaps(12, 18));
+        assert!(!set.overlaps(0, 10));
+        assert!(!set.overlaps(20, 30));
+    }
+}
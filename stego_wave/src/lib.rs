@@ -1,6 +1,15 @@
+pub mod auth;
+pub mod command;
+pub mod compression;
+pub mod crypto;
 pub mod error;
 pub mod formats;
+pub mod interval_set;
+pub mod metrics;
 pub mod object;
+pub mod riff;
+pub mod share;
+pub mod tls;
 
 pub use object::AudioSteganography;
 pub mod configuration;
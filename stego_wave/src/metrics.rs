@@ -0,0 +1,87 @@
+use crate::error::StegoError;
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use std::time::Duration;
+
+/// Failure bucket recorded against `stego_wave_operation_failures_total`, mirroring
+/// the two outcomes servers map a [`StegoError`] onto for their transport's status
+/// codes (`400`/`invalid_argument` vs `500`/`internal`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    InvalidArgument,
+    Internal,
+}
+
+impl FailureKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            FailureKind::InvalidArgument => "invalid_argument",
+            FailureKind::Internal => "internal",
+        }
+    }
+}
+
+/// Classifies `err` the same way the gRPC and REST servers classify it for their
+/// own status codes, so the failure counter's buckets line up with what callers see.
+pub fn failure_kind_for(err: &StegoError) -> FailureKind {
+    match err {
+        StegoError::IncorrectPassword
+        | StegoError::IntegrityCheckFailed
+        | StegoError::NotEnoughSamples(_) => FailureKind::InvalidArgument,
+        _ => FailureKind::Internal,
+    }
+}
+
+/// Installs the process-wide Prometheus recorder and returns the handle used to
+/// render the `/metrics` response.
+pub fn install_recorder() -> PrometheusHandle {
+    PrometheusBuilder::new()
+        .install_recorder()
+        .expect("failed to install Prometheus recorder")
+}
+
+/// Records a successful `operation` (`hide_message`, `extract_message`, `clear_message`),
+/// labeled by `format`/`lsb_deep`, along with its processing latency and input size.
+pub fn record_success(
+    operation: &'static str,
+    format: &str,
+    lsb_deep: u8,
+    input_len: usize,
+    elapsed: Duration,
+) {
+    let format = format.to_string();
+    let lsb_deep = lsb_deep.to_string();
+
+    metrics::counter!(
+        "stego_wave_operations_total",
+        "operation" => operation, "format" => format.clone(), "lsb_deep" => lsb_deep.clone()
+    )
+    .increment(1);
+    metrics::histogram!(
+        "stego_wave_operation_duration_seconds",
+        "operation" => operation, "format" => format.clone(), "lsb_deep" => lsb_deep.clone()
+    )
+    .record(elapsed.as_secs_f64());
+    metrics::histogram!(
+        "stego_wave_operation_input_bytes",
+        "operation" => operation, "format" => format, "lsb_deep" => lsb_deep
+    )
+    .record(input_len as f64);
+}
+
+/// Records a failed `operation`, labeled by `format`/`lsb_deep` and bucketed by
+/// `kind` so dashboards can split client errors from server errors.
+pub fn record_failure(operation: &'static str, format: &str, lsb_deep: u8, kind: FailureKind) {
+    let format = format.to_string();
+    let lsb_deep = lsb_deep.to_string();
+
+    metrics::counter!(
+        "stego_wave_operations_total",
+        "operation" => operation, "format" => format.clone(), "lsb_deep" => lsb_deep.clone()
+    )
+    .increment(1);
+    metrics::counter!(
+        "stego_wave_operation_failures_total",
+        "operation" => operation, "format" => format, "lsb_deep" => lsb_deep, "kind" => kind.as_str()
+    )
+    .increment(1);
+}
@@ -2,7 +2,7 @@ use crate::error::{StegoError, StegoWaveClientError};
 use hound::WavSpec;
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::path::{Path, PathBuf};
 
@@ -19,13 +19,19 @@ pub trait AudioSteganography<S> {
         file_output: impl Into<PathBuf>,
         message: impl Into<String>,
         password: impl Into<String>,
+        encrypt: bool,
     ) -> ResultStego<()>;
 
+    /// `encrypt` selects whether [`crate::crypto::encrypt`] actually AEAD-seals
+    /// `message` or leaves it as plaintext padded to the same envelope size;
+    /// either way the embedded payload is exactly [`crate::crypto::OVERHEAD_LEN`]
+    /// bytes larger than `message`, so capacity math never depends on this flag.
     fn hide_message_binary(
         &self,
         samples: &mut [S],
         message: &str,
         password: &str,
+        encrypt: bool,
     ) -> ResultStego<()>;
 
     fn extract_message(
@@ -41,10 +47,196 @@ pub trait AudioSteganography<S> {
     fn read_samples_from_byte(&self, byte: Vec<u8>) -> ResultStego<(Vec<S>, AudioFileSpec)>;
     fn write_samples_to_byte(&self, spec: AudioFileSpec, samples: &[S]) -> ResultStego<Vec<u8>>;
     fn default_filename(&self) -> String;
+    fn format_id(&self) -> u8;
+
+    /// Maximum number of message bytes that fit in `sample_len` samples, after the
+    /// header and encryption overhead ([`AudioSteganography::overhead_bytes`]) is
+    /// accounted for.
+    fn capacity_bytes(&self, sample_len: usize) -> usize;
+
+    /// Fixed number of bytes consumed by the header and encryption envelope on top
+    /// of the message itself, regardless of carrier size.
+    fn overhead_bytes(&self) -> usize;
+
+    /// Reads `byte` as a carrier file and reports `(capacity_bytes, overhead_bytes)`
+    /// so callers can validate a message fits before attempting to hide it.
+    fn read_capacity_from_byte(&self, byte: Vec<u8>) -> ResultStego<(usize, usize)>
+    where
+        Self: Sized,
+    {
+        let (samples, _spec) = self.read_samples_from_byte(byte)?;
+        Ok((self.capacity_bytes(samples.len()), self.overhead_bytes()))
+    }
+
+    /// Like [`hide_message_binary`](Self::hide_message_binary), but calls
+    /// `on_progress(processed, total)` as samples are embedded into, so a streaming
+    /// gateway (see [`crate::api`] in the REST service crate) can surface an
+    /// incremental progress bar instead of blocking silently on large carriers.
+    ///
+    /// The default just brackets the existing method with a `0%` and a `100%`
+    /// tick; [`crate::formats::wav::WAV16`] overrides it with real per-chunk
+    /// reporting via [`ProgressIndices`]. Formats that don't override it still
+    /// behave correctly, they just don't have anything incremental to report.
+    fn hide_message_binary_with_progress(
+        &self,
+        samples: &mut [S],
+        message: &str,
+        password: &str,
+        encrypt: bool,
+        on_progress: &mut dyn FnMut(usize, usize),
+    ) -> ResultStego<()> {
+        on_progress(0, samples.len());
+        self.hide_message_binary(samples, message, password, encrypt)?;
+        on_progress(samples.len(), samples.len());
+        Ok(())
+    }
+}
+
+/// Iterator adapter that calls `on_progress(consumed, total)` every `report_every`
+/// items pulled from the wrapped index iterator, without threading a progress
+/// callback through [`crate::formats::wav::write_bits`] and friends directly.
+/// `total` is fixed at construction (typically the sample buffer length); `consumed`
+/// is the running count of indices yielded so far.
+pub struct ProgressIndices<'a, I> {
+    inner: I,
+    consumed: usize,
+    total: usize,
+    report_every: usize,
+    on_progress: &'a mut dyn FnMut(usize, usize),
+}
+
+impl<'a, I> ProgressIndices<'a, I> {
+    pub fn new(inner: I, total: usize, report_every: usize, on_progress: &'a mut dyn FnMut(usize, usize)) -> Self {
+        Self {
+            inner,
+            consumed: 0,
+            total,
+            report_every: report_every.max(1),
+            on_progress,
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item = usize>> Iterator for ProgressIndices<'a, I> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let next = self.inner.next();
+        if next.is_some() {
+            self.consumed += 1;
+            if self.consumed % self.report_every == 0 {
+                (self.on_progress)(self.consumed, self.total);
+            }
+        }
+        next
+    }
 }
 
 pub enum AudioFileSpec {
     Wav(WavSpec),
+    Flac(FlacSpec),
+}
+
+/// Carrier parameters read from a FLAC stream's `STREAMINFO` block, reapplied when
+/// re-encoding so [`crate::formats::flac::FLAC`]'s output stream matches the input's
+/// channel count, sample rate, and bit depth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlacSpec {
+    pub channels: u32,
+    pub sample_rate: u32,
+    pub bits_per_sample: u32,
+}
+
+/// Type-erased sample buffer produced by [`crate::formats::StegoFormat`]. The
+/// active variant always matches the concrete codec that read it, since each
+/// codec only ever hands back the width it reads samples as.
+pub enum StegoSamples {
+    I8(Vec<i8>),
+    I16(Vec<i16>),
+    I32(Vec<i32>),
+    F32(Vec<f32>),
+}
+
+impl StegoSamples {
+    pub fn len(&self) -> usize {
+        match self {
+            StegoSamples::I8(samples) => samples.len(),
+            StegoSamples::I16(samples) => samples.len(),
+            StegoSamples::I32(samples) => samples.len(),
+            StegoSamples::F32(samples) => samples.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A PCM sample width the embedding pipeline can read, mask, and write directly.
+///
+/// Implemented for every integer width `hound` can decode samples into (`i8`,
+/// `i16`, `i32` — the latter also backs 24-bit samples, which hound widens to
+/// `i32` on read). [`ByteIterator`] and the mask-based embed/extract/clear
+/// helpers in [`crate::formats::wav`] are generic over this trait, so each bit
+/// depth reuses the same index-walking logic instead of a copy per width.
+pub trait StegoSample: Copy + hound::Sample {
+    /// Builds a mask with its lowest `lsb_deep` bits set and every other bit zeroed.
+    fn mask_for(lsb_deep: u8) -> Self;
+
+    /// Reads the bits of `self` covered by `mask` as an unsigned value, independent
+    /// of this type's sign representation.
+    fn low_bits(self, mask: Self) -> u32;
+
+    /// Replaces the bits covered by `mask` with `value`'s low bits, leaving every
+    /// other bit of `self` untouched.
+    fn with_low_bits(self, mask: Self, value: u32) -> Self;
+}
+
+macro_rules! impl_stego_sample {
+    ($t:ty, $unsigned:ty) => {
+        impl StegoSample for $t {
+            fn mask_for(lsb_deep: u8) -> Self {
+                let mask: i64 = (1i64 << lsb_deep) - 1;
+                mask as $t
+            }
+
+            fn low_bits(self, mask: Self) -> u32 {
+                ((self & mask) as $unsigned) as u32
+            }
+
+            fn with_low_bits(self, mask: Self, value: u32) -> Self {
+                (self & !mask) | (value as $unsigned as $t)
+            }
+        }
+    };
+}
+
+impl_stego_sample!(i8, u8);
+impl_stego_sample!(i16, u16);
+impl_stego_sample!(i32, u32);
+
+/// Embeds into the low bits of the IEEE-754 mantissa rather than the sample's
+/// numeric value, via `to_bits()`/`from_bits()`. `with_low_bits` leaves NaN/±Infinity
+/// samples (exponent all-ones) untouched so embedding never manufactures a NaN;
+/// pairing this with an index source that skips non-finite samples (see
+/// `formats::wav::WAV32F`) keeps hide/extract symmetric.
+impl StegoSample for f32 {
+    fn mask_for(lsb_deep: u8) -> Self {
+        let mask: u32 = (1u32 << lsb_deep) - 1;
+        f32::from_bits(mask)
+    }
+
+    fn low_bits(self, mask: Self) -> u32 {
+        self.to_bits() & mask.to_bits()
+    }
+
+    fn with_low_bits(self, mask: Self, value: u32) -> Self {
+        if !self.is_finite() {
+            return self;
+        }
+        let bits = (self.to_bits() & !mask.to_bits()) | (value & mask.to_bits());
+        f32::from_bits(bits)
+    }
 }
 
 #[derive(Clone)]
@@ -95,10 +287,206 @@ impl Iterator for UniqueRandomIndices {
     }
 }
 
+/// Which interleaved channel(s) embedding is allowed to touch in a multi-channel
+/// carrier, instead of the default of picking uniformly at random across every
+/// sample regardless of which channel it belongs to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EmbedChannel {
+    /// Spread across every channel, picked independently at random (the original
+    /// behavior, and the only option that applies to a mono carrier).
+    #[default]
+    All,
+    /// Only touch one channel (0-indexed), leaving the others untouched as a
+    /// clean reference.
+    Single(u16),
+    /// Cycle through every channel in interleaved order, one sample per channel
+    /// per frame, instead of scattering bits across frames at random.
+    RoundRobin,
+}
+
+/// Channel-aware wrapper over [`UniqueRandomIndices`]: walks randomly chosen
+/// interleaved *frames* rather than raw (flat) sample indices, then expands each
+/// frame into the real sample index/indices the selected [`EmbedChannel`] policy
+/// calls for. Reduces to a plain [`UniqueRandomIndices`] over the whole buffer
+/// under [`EmbedChannel::All`].
+#[derive(Clone)]
+pub struct ChannelIndices {
+    embed_channel: EmbedChannel,
+    channels: usize,
+    frames: UniqueRandomIndices,
+    pending: VecDeque<usize>,
+}
+
+impl ChannelIndices {
+    /// `sample_len` is the flat (interleaved) sample count; `channels` is the
+    /// carrier's channel count (`reader.spec().channels`).
+    pub fn new(
+        sample_len: usize,
+        channels: u16,
+        embed_channel: EmbedChannel,
+        password: &str,
+        max_occupancy: usize,
+    ) -> Self {
+        let channels = channels.max(1) as usize;
+
+        let frame_space = match embed_channel {
+            EmbedChannel::All => sample_len,
+            EmbedChannel::Single(_) | EmbedChannel::RoundRobin => sample_len / channels,
+        };
+
+        Self {
+            embed_channel,
+            channels,
+            frames: UniqueRandomIndices::new(frame_space, password, max_occupancy),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Number of flat sample indices this policy can ever draw from, given a
+    /// carrier of `sample_len` interleaved samples with `channels` channels.
+    /// Used to size capacity checks instead of the raw sample count.
+    pub fn usable_sample_count(sample_len: usize, channels: u16, embed_channel: EmbedChannel) -> usize {
+        let channels = channels.max(1) as usize;
+        match embed_channel {
+            EmbedChannel::All => sample_len,
+            EmbedChannel::Single(_) => sample_len / channels,
+            EmbedChannel::RoundRobin => (sample_len / channels) * channels,
+        }
+    }
+}
+
+impl Iterator for ChannelIndices {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if let Some(index) = self.pending.pop_front() {
+            return Some(index);
+        }
+
+        match self.embed_channel {
+            EmbedChannel::All => self.frames.next(),
+            EmbedChannel::Single(channel) => {
+                let frame = self.frames.next()?;
+                Some(frame * self.channels + channel as usize)
+            }
+            EmbedChannel::RoundRobin => {
+                let frame = self.frames.next()?;
+                let base = frame * self.channels;
+                self.pending.extend(base..base + self.channels);
+                self.pending.pop_front()
+            }
+        }
+    }
+}
+
+/// Magic bytes identifying a `stego_wave` payload header.
+pub const STEGO_MAGIC: [u8; 4] = *b"SW01";
+
+/// Bit depth the self-describing header is always embedded at, regardless of the
+/// `lsb_deep` used for the payload that follows it. Fixing it lets extraction learn
+/// the real `lsb_deep`/format straight from the carrier instead of requiring the
+/// caller to supply it.
+pub const HEADER_LSB_DEEP: u8 = 1;
+
+pub(crate) const MAX_VARINT_BYTES: usize = 5;
+
+/// Worst-case byte length of an encoded [`PayloadHeader`]: magic + `lsb_deep` +
+/// `format_id` + a maximally-sized varint length.
+pub const HEADER_MAX_LEN: usize = STEGO_MAGIC.len() + 2 + MAX_VARINT_BYTES;
+
+fn encode_varint(mut value: u64) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(MAX_VARINT_BYTES);
+
+    loop {
+        let mut byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        bytes.push(byte);
+
+        if value == 0 {
+            break;
+        }
+    }
+
+    bytes
+}
+
+fn decode_varint(byte_iter: &mut impl Iterator<Item = u8>) -> ResultStego<u64> {
+    let mut value: u64 = 0;
+
+    for shift in (0..MAX_VARINT_BYTES).map(|i| i * 7) {
+        let byte = byte_iter.next().ok_or(StegoError::FailedToReceiveMessage)?;
+        value |= ((byte & 0x7F) as u64) << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(StegoError::FailedToReceiveMessage)
+}
+
+/// Compact, self-describing header written ahead of the encrypted payload.
+///
+/// Carries everything extraction needs to read the rest of the carrier on its own:
+/// a magic marker (acts as an early, password-dependent sanity check), the `lsb_deep`
+/// and format id the payload was embedded with, and its exact byte length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadHeader {
+    pub lsb_deep: u8,
+    pub format_id: u8,
+    pub payload_len: u64,
+}
+
+impl PayloadHeader {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(STEGO_MAGIC.len() + 2 + MAX_VARINT_BYTES);
+        bytes.extend_from_slice(&STEGO_MAGIC);
+        bytes.push(self.lsb_deep);
+        bytes.push(self.format_id);
+        bytes.extend(encode_varint(self.payload_len));
+
+        bytes
+    }
+
+    /// Reads a header off `byte_iter`, rejecting streams whose magic doesn't match
+    /// (wrong password) or whose declared length exceeds `max_payload_len` (corrupted
+    /// carrier / wrong password that happened to clear the magic check).
+    pub fn decode(
+        byte_iter: &mut impl Iterator<Item = u8>,
+        max_payload_len: u64,
+    ) -> ResultStego<Self> {
+        let mut magic = [0u8; STEGO_MAGIC.len()];
+        for byte in &mut magic {
+            *byte = byte_iter.next().ok_or(StegoError::FailedToReceiveMessage)?;
+        }
+
+        if magic != STEGO_MAGIC {
+            return Err(StegoError::IncorrectPassword);
+        }
+
+        let lsb_deep = byte_iter.next().ok_or(StegoError::FailedToReceiveMessage)?;
+        let format_id = byte_iter.next().ok_or(StegoError::FailedToReceiveMessage)?;
+        let payload_len = decode_varint(byte_iter)?;
+
+        if payload_len > max_payload_len {
+            return Err(StegoError::FailedToReceiveMessage);
+        }
+
+        Ok(Self {
+            lsb_deep,
+            format_id,
+            payload_len,
+        })
+    }
+}
+
 pub struct ByteIterator<'a, I, T> {
     samples: &'a [T],
     indices_iter: I,
-    mask: i16,
+    mask: T,
     lsb_deep: u8,
     current_byte: u8,
     current_bit: u8,
@@ -113,7 +501,7 @@ where
     pub fn new(
         samples: &'a [T],
         indices_iter: I,
-        mask: i16,
+        mask: T,
         lsb_deep: u8,
         current_byte: u8,
         current_bit: u8,
@@ -131,9 +519,10 @@ where
     }
 }
 
-impl<I> Iterator for ByteIterator<'_, I, i16>
+impl<I, T> Iterator for ByteIterator<'_, I, T>
 where
     I: Iterator<Item = usize>,
+    T: StegoSample,
 {
     type Item = u8;
 
@@ -149,7 +538,7 @@ where
         let mut full_read = false;
 
         while let Some(sample_index) = self.indices_iter.next() {
-            let encoded = (self.samples[sample_index] & self.mask) as u16;
+            let encoded = self.samples[sample_index].low_bits(self.mask);
 
             for shift in (0..self.lsb_deep).rev() {
                 let bit = ((encoded >> shift) & 1) as u8;
@@ -173,7 +562,11 @@ where
     }
 }
 
-#[async_trait::async_trait]
+// wasm32 futures carry `JsValue`s internally and are never `Send`, so the trait
+// drops the bound there; every other target keeps it so `Box<dyn StegoWaveClient>`
+// stays usable across `tokio::spawn`.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 pub trait StegoWaveClient: Sync + Send {
     async fn hide_message(
         &mut self,
@@ -182,6 +575,8 @@ pub trait StegoWaveClient: Sync + Send {
         password: String,
         format: String,
         lsb_deep: u8,
+        compress: bool,
+        encrypt: bool,
     ) -> Result<Vec<u8>, StegoWaveClientError>;
 
     async fn extract_message(
@@ -199,11 +594,62 @@ pub trait StegoWaveClient: Sync + Send {
         format: String,
         lsb_deep: u8,
     ) -> Result<Vec<u8>, StegoWaveClientError>;
+
+    /// Reports `(capacity_bytes, overhead_bytes)` for `file` without hiding anything,
+    /// so a caller can size a message or pick `lsb_deep` before uploading it for real.
+    /// Mirrors [`StegoFormat::read_capacity_from_byte`](crate::formats::StegoFormat::read_capacity_from_byte)
+    /// across the network instead of requiring the library linked locally.
+    async fn capacity(
+        &mut self,
+        file: Vec<u8>,
+        format: String,
+        lsb_deep: u8,
+    ) -> Result<(usize, usize), StegoWaveClientError>;
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{ByteIterator, UniqueRandomIndices};
+    use super::{ByteIterator, PayloadHeader, STEGO_MAGIC, UniqueRandomIndices};
+
+    #[test]
+    fn test_payload_header_roundtrip() {
+        let header = PayloadHeader {
+            lsb_deep: 3,
+            format_id: 0,
+            payload_len: 1_024,
+        };
+
+        let encoded = header.encode();
+        let decoded = PayloadHeader::decode(&mut encoded.into_iter(), u64::MAX).unwrap();
+
+        assert_eq!(decoded, header);
+    }
+
+    #[test]
+    fn test_payload_header_rejects_bad_magic() {
+        let mut bytes = vec![0, 0, 0, 0, 1, 0, 5];
+        assert!(PayloadHeader::decode(&mut bytes.drain(..), u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_payload_header_rejects_over_capacity_length() {
+        let header = PayloadHeader {
+            lsb_deep: 1,
+            format_id: 0,
+            payload_len: 1_000,
+        };
+
+        let encoded = header.encode();
+        assert!(matches!(
+            PayloadHeader::decode(&mut encoded.into_iter(), 10),
+            Err(super::StegoError::FailedToReceiveMessage)
+        ));
+    }
+
+    #[test]
+    fn test_magic_is_four_bytes() {
+        assert_eq!(STEGO_MAGIC.len(), 4);
+    }
 
     fn inner_func(iter: &mut UniqueRandomIndices) {
         for x in iter {
@@ -0,0 +1,166 @@
+//! Minimal RIFF/WAVE chunk reader and splicer for the non-essential chunks
+//! `hound` doesn't round-trip: `LIST INFO` tags (artist/title/comment, ...) and the
+//! `fact` chunk present on some non-PCM WAV files.
+//!
+//! `hound::WavWriter` only ever emits `fmt ` and `data`, so rewriting a WAV through
+//! it silently drops everything else a source file carried — itself a steganalysis
+//! red flag (a file that suspiciously lost its metadata). [`read`] pulls those
+//! chunks out of a source file's raw bytes up front; [`splice`] re-appends them to
+//! a freshly-written `hound` buffer.
+
+/// Non-essential RIFF chunks carried alongside the PCM audio. Also usable as an
+/// input: callers can build one by hand to inject tags into a file that never had
+/// them, or inspect one read from a source file before reusing it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RiffMetadata {
+    /// `LIST INFO` tags such as `IART` (artist), `INAM` (title), `ICMT` (comment),
+    /// keyed by their four-character RIFF chunk id, in file order.
+    pub info_tags: Vec<(String, String)>,
+    /// Raw bytes of the `fact` chunk, present on some non-PCM WAV files.
+    pub fact_chunk: Option<Vec<u8>>,
+}
+
+/// Byte length of the leading `"RIFF" + size(4) + "WAVE"` header before the chunk table starts.
+const RIFF_HEADER_LEN: usize = 12;
+
+/// Walks `bytes` as a RIFF/WAVE file and collects its `LIST INFO` tags and `fact`
+/// chunk, if present. Returns an empty [`RiffMetadata`] for anything that isn't a
+/// well-formed RIFF/WAVE buffer, rather than failing: metadata preservation is a
+/// best-effort nicety, not something that should block embedding.
+pub fn read(bytes: &[u8]) -> RiffMetadata {
+    let mut metadata = RiffMetadata::default();
+
+    if bytes.len() < RIFF_HEADER_LEN || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return metadata;
+    }
+
+    let mut pos = RIFF_HEADER_LEN;
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let size = u32::from_le_bytes(bytes[pos + 4..pos + 8].try_into().unwrap()) as usize;
+        let data_start = pos + 8;
+        let data_end = (data_start + size).min(bytes.len());
+        let data = &bytes[data_start..data_end];
+
+        match id {
+            b"fact" => metadata.fact_chunk = Some(data.to_vec()),
+            b"LIST" if data.len() >= 4 && &data[0..4] == b"INFO" => {
+                metadata.info_tags = parse_info_list(&data[4..]);
+            }
+            _ => {}
+        }
+
+        pos = data_start + size + (size % 2);
+    }
+
+    metadata
+}
+
+fn parse_info_list(mut data: &[u8]) -> Vec<(String, String)> {
+    let mut tags = Vec::new();
+
+    while data.len() >= 8 {
+        let id = String::from_utf8_lossy(&data[0..4]).to_string();
+        let size = u32::from_le_bytes(data[4..8].try_into().unwrap()) as usize;
+        let value_end = (8 + size).min(data.len());
+        let value = String::from_utf8_lossy(&data[8..value_end])
+            .trim_end_matches('\0')
+            .to_string();
+        tags.push((id, value));
+
+        let consumed = value_end + (size % 2);
+        if consumed == 0 || consumed > data.len() {
+            break;
+        }
+        data = &data[consumed..];
+    }
+
+    tags
+}
+
+/// Re-appends `metadata`'s chunks to `base` (a freshly `hound`-written RIFF/WAVE
+/// buffer containing only `fmt `/`data`), fixing up the top-level RIFF size.
+/// Returns `base` unchanged if `metadata` is empty.
+pub fn splice(base: &[u8], metadata: &RiffMetadata) -> Vec<u8> {
+    if metadata.info_tags.is_empty() && metadata.fact_chunk.is_none() {
+        return base.to_vec();
+    }
+
+    let mut extra = Vec::new();
+    if !metadata.info_tags.is_empty() {
+        extra.extend(encode_info_list(&metadata.info_tags));
+    }
+    if let Some(fact) = &metadata.fact_chunk {
+        extra.extend(encode_chunk(b"fact", fact));
+    }
+
+    let mut out = Vec::with_capacity(base.len() + extra.len());
+    out.extend_from_slice(base);
+    out.extend_from_slice(&extra);
+
+    let riff_size = (out.len() - 8) as u32;
+    out[4..8].copy_from_slice(&riff_size.to_le_bytes());
+
+    out
+}
+
+fn encode_chunk(id: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut chunk = Vec::with_capacity(8 + data.len() + 1);
+    chunk.extend_from_slice(id);
+    chunk.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    chunk.extend_from_slice(data);
+    if data.len() % 2 == 1 {
+        chunk.push(0);
+    }
+    chunk
+}
+
+fn encode_info_list(tags: &[(String, String)]) -> Vec<u8> {
+    let mut info_body = Vec::new();
+    info_body.extend_from_slice(b"INFO");
+
+    for (id, value) in tags {
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.push(0);
+        let id_bytes: [u8; 4] = id.as_bytes().try_into().unwrap_or(*b"ICMT");
+        info_body.extend(encode_chunk(&id_bytes, &bytes));
+    }
+
+    encode_chunk(b"LIST", &info_body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_info_tags_and_fact_chunk() {
+        let metadata = RiffMetadata {
+            info_tags: vec![
+                ("IART".to_string(), "Test Artist".to_string()),
+                ("INAM".to_string(), "Test Title".to_string()),
+            ],
+            fact_chunk: Some(vec![4, 0, 0, 0]),
+        };
+
+        let base = b"RIFF\x24\x00\x00\x00WAVEfmt \x00\x00\x00\x00".to_vec();
+        let spliced = splice(&base, &metadata);
+
+        let parsed = read(&spliced);
+        assert_eq!(parsed.info_tags, metadata.info_tags);
+        assert_eq!(parsed.fact_chunk, metadata.fact_chunk);
+    }
+
+    #[test]
+    fn read_returns_empty_metadata_for_non_riff_bytes() {
+        let metadata = read(b"not a wav file");
+        assert_eq!(metadata, RiffMetadata::default());
+    }
+
+    #[test]
+    fn splice_is_a_no_op_for_empty_metadata() {
+        let base = b"RIFF\x24\x00\x00\x00WAVEfmt \x00\x00\x00\x00".to_vec();
+        let spliced = splice(&base, &RiffMetadata::default());
+        assert_eq!(spliced, base);
+    }
+}
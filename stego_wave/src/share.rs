@@ -0,0 +1,216 @@
+use rand::RngCore;
+use rand::rngs::OsRng;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+const TOKEN_LEN: usize = 32;
+
+fn default_max_entries() -> usize {
+    1024
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ShareConfig {
+    pub share_expiry_duration: u64,
+    /// Caps how many entries the store holds at once. Once full, [`ShareStore::register`]
+    /// evicts whichever entry is nearest to expiring to make room for the new one.
+    #[serde(default = "default_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for ShareConfig {
+    fn default() -> Self {
+        Self {
+            share_expiry_duration: 0,
+            max_entries: default_max_entries(),
+        }
+    }
+}
+
+struct ShareEntry {
+    bytes: Vec<u8>,
+    expires_at: SystemTime,
+}
+
+/// Backend storing processed audio under a random token for one-shot retrieval by link.
+///
+/// This decouples the expensive hide/clear step from delivery: a gateway that just
+/// produced stego output can hand back a short-lived `/share/{token}` link instead of
+/// the bytes themselves, so a caller can retry the request cheaply or hand the link
+/// to a browser's native downloader. Gateways depend on `dyn ShareBackend` rather than
+/// [`ShareStore`] directly so an alternative backend (e.g. one backed by Redis or a
+/// disk-backed cache) can be swapped in without touching call sites.
+pub trait ShareBackend: Send + Sync {
+    /// Registers `bytes` for one-shot retrieval and returns the token naming them.
+    fn register(&self, bytes: Vec<u8>) -> String;
+
+    /// Removes and returns the bytes registered under `token`, if present and not expired.
+    ///
+    /// The link is one-shot: whether this returns `Some` or `None`, the entry is gone
+    /// from the store afterward.
+    fn take(&self, token: &str) -> Option<Vec<u8>>;
+
+    /// Drops every registered entry whose expiry has passed without being fetched.
+    ///
+    /// Meant to be driven periodically by a background task, since entries are
+    /// in-memory only and must not accumulate forever when a caller never collects them.
+    fn sweep_expired(&self);
+}
+
+/// Default in-memory [`ShareBackend`], guarded by a pair of mutexes: one for the
+/// entries themselves, one for the expiry-ordered index used by both eviction paths
+/// (TTL sweep and over-capacity eviction in [`ShareStore::register`]).
+pub struct ShareStore {
+    entries: Mutex<HashMap<String, ShareEntry>>,
+    expiry_order: Mutex<BTreeMap<SystemTime, Vec<String>>>,
+    expiry_duration: Duration,
+    max_entries: usize,
+}
+
+impl ShareStore {
+    pub fn new(config: &ShareConfig) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            expiry_order: Mutex::new(BTreeMap::new()),
+            expiry_duration: Duration::from_secs(config.share_expiry_duration),
+            max_entries: config.max_entries,
+        }
+    }
+
+    /// Evicts the single entry nearest to expiring, if any are registered.
+    ///
+    /// Called with `expiry_order` already locked so the eviction is atomic with the
+    /// capacity check in [`ShareStore::register`] that triggers it.
+    fn evict_oldest(
+        &self,
+        expiry_order: &mut BTreeMap<SystemTime, Vec<String>>,
+        entries: &mut HashMap<String, ShareEntry>,
+    ) {
+        let Some((&expiry, _)) = expiry_order.iter().next() else {
+            return;
+        };
+
+        if let Some(tokens) = expiry_order.remove(&expiry) {
+            for token in tokens {
+                entries.remove(&token);
+            }
+        }
+    }
+}
+
+impl ShareBackend for ShareStore {
+    fn register(&self, bytes: Vec<u8>) -> String {
+        let token = generate_token();
+        let expires_at = SystemTime::now() + self.expiry_duration;
+
+        let mut entries = self.entries.lock().unwrap();
+        let mut expiry_order = self.expiry_order.lock().unwrap();
+
+        if entries.len() >= self.max_entries {
+            self.evict_oldest(&mut expiry_order, &mut entries);
+        }
+
+        entries.insert(
+            token.clone(),
+            ShareEntry {
+                bytes,
+                expires_at,
+            },
+        );
+        expiry_order.entry(expires_at).or_default().push(token.clone());
+
+        token
+    }
+
+    fn take(&self, token: &str) -> Option<Vec<u8>> {
+        let entry = self.entries.lock().unwrap().remove(token)?;
+
+        if entry.expires_at <= SystemTime::now() {
+            return None;
+        }
+
+        Some(entry.bytes)
+    }
+
+    fn sweep_expired(&self) {
+        let now = SystemTime::now();
+        let mut expiry_order = self.expiry_order.lock().unwrap();
+
+        let expired: Vec<SystemTime> = expiry_order
+            .range(..=now)
+            .map(|(&expiry, _)| expiry)
+            .collect();
+
+        let mut entries = self.entries.lock().unwrap();
+        for expiry in expired {
+            if let Some(tokens) = expiry_order.remove(&expiry) {
+                for token in tokens {
+                    entries.remove(&token);
+                }
+            }
+        }
+    }
+}
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; TOKEN_LEN];
+    OsRng.fill_bytes(&mut bytes);
+
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(share_expiry_duration: u64) -> ShareStore {
+        store_with_capacity(share_expiry_duration, default_max_entries())
+    }
+
+    fn store_with_capacity(share_expiry_duration: u64, max_entries: usize) -> ShareStore {
+        ShareStore::new(&ShareConfig {
+            share_expiry_duration,
+            max_entries,
+        })
+    }
+
+    #[test]
+    fn registered_bytes_can_be_taken_once() {
+        let store = store(60);
+        let token = store.register(vec![1, 2, 3]);
+
+        assert_eq!(store.take(&token), Some(vec![1, 2, 3]));
+        assert_eq!(store.take(&token), None);
+    }
+
+    #[test]
+    fn sweep_drops_expired_entries() {
+        let store = store(0);
+        let token = store.register(vec![1, 2, 3]);
+        std::thread::sleep(Duration::from_millis(10));
+
+        store.sweep_expired();
+
+        assert_eq!(store.take(&token), None);
+    }
+
+    #[test]
+    fn unknown_token_is_not_found() {
+        let store = store(60);
+
+        assert_eq!(store.take("not-a-real-token"), None);
+    }
+
+    #[test]
+    fn register_evicts_soonest_to_expire_entry_once_at_capacity() {
+        let store = store_with_capacity(60, 2);
+        let first = store.register(vec![1]);
+        let _second = store.register(vec![2]);
+        let third = store.register(vec![3]);
+
+        assert_eq!(store.take(&first), None);
+        assert_eq!(store.take(&third), Some(vec![3]));
+    }
+}
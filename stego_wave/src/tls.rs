@@ -0,0 +1,51 @@
+use std::fs;
+
+use serde::Deserialize;
+
+use crate::error::StegoError;
+
+/// TLS settings shared by the REST and gRPC servers.
+///
+/// Audio steganography payloads are sensitive, so leaving `tls_cert_path`/
+/// `tls_key_path` unset is only honoured when `insecure` is explicitly set —
+/// a gateway should not silently fall back to cleartext.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct TlsConfig {
+    pub tls_cert_path: Option<String>,
+    pub tls_key_path: Option<String>,
+    #[serde(default)]
+    pub insecure: bool,
+}
+
+/// The raw PEM bytes of a certificate chain and its private key, read from the
+/// paths named in a [`TlsConfig`].
+pub struct TlsMaterial {
+    pub cert_pem: Vec<u8>,
+    pub key_pem: Vec<u8>,
+}
+
+impl TlsConfig {
+    /// Reads the configured certificate/key pair.
+    ///
+    /// Returns `Ok(None)` only when no paths were given and `insecure` is set,
+    /// so a gateway can bind plain HTTP/gRPC as a deliberate, logged choice
+    /// rather than a default.
+    pub fn load(&self) -> Result<Option<TlsMaterial>, StegoError> {
+        match (&self.tls_cert_path, &self.tls_key_path) {
+            (Some(cert_path), Some(key_path)) => {
+                let cert_pem =
+                    fs::read(cert_path).map_err(|err| StegoError::Other(err.to_string()))?;
+                let key_pem =
+                    fs::read(key_path).map_err(|err| StegoError::Other(err.to_string()))?;
+                Ok(Some(TlsMaterial { cert_pem, key_pem }))
+            }
+            (None, None) if self.insecure => Ok(None),
+            (None, None) => Err(StegoError::Other(
+                "No TLS certificate/key configured; set tls_cert_path and tls_key_path, or set insecure = true to serve plaintext".to_string(),
+            )),
+            _ => Err(StegoError::Other(
+                "tls_cert_path and tls_key_path must both be set".to_string(),
+            )),
+        }
+    }
+}
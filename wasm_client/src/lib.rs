@@ -0,0 +1,98 @@
+mod wasm_client;
+
+use js_sys::Uint8Array;
+use stego_wave::object::StegoWaveClient;
+use wasm_bindgen::prelude::*;
+
+pub use wasm_client::StegoWaveWasmClient;
+
+/// Hides `message` inside `file` and returns the resulting audio bytes.
+#[wasm_bindgen(js_name = hideMessage)]
+pub async fn hide_message(
+    server_url: String,
+    file: Uint8Array,
+    message: String,
+    password: String,
+    format: String,
+    lsb_deep: u8,
+    compress: bool,
+    encrypt: bool,
+) -> Result<Uint8Array, JsValue> {
+    let mut client = new_client(&server_url)?;
+    let result = client
+        .hide_message(
+            file.to_vec(),
+            message,
+            password,
+            format,
+            lsb_deep,
+            compress,
+            encrypt,
+        )
+        .await
+        .map_err(|err| JsValue::from_str(&err.to_string()))?;
+
+    Ok(Uint8Array::from(result.as_slice()))
+}
+
+/// Extracts the secret message hidden inside `file`.
+#[wasm_bindgen(js_name = extractMessage)]
+pub async fn extract_message(
+    server_url: String,
+    file: Uint8Array,
+    password: String,
+    format: String,
+    lsb_deep: u8,
+) -> Result<String, JsValue> {
+    let mut client = new_client(&server_url)?;
+    client
+        .extract_message(file.to_vec(), password, format, lsb_deep)
+        .await
+        .map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Clears the secret message hidden inside `file` and returns the cleaned audio bytes.
+#[wasm_bindgen(js_name = clearMessage)]
+pub async fn clear_message(
+    server_url: String,
+    file: Uint8Array,
+    password: String,
+    format: String,
+    lsb_deep: u8,
+) -> Result<Uint8Array, JsValue> {
+    let mut client = new_client(&server_url)?;
+    let result = client
+        .clear_message(file.to_vec(), password, format, lsb_deep)
+        .await
+        .map_err(|err| JsValue::from_str(&err.to_string()))?;
+
+    Ok(Uint8Array::from(result.as_slice()))
+}
+
+/// Reports `(capacity_bytes, overhead_bytes)` for `file` without hiding anything.
+#[wasm_bindgen(js_name = capacity)]
+pub async fn capacity(
+    server_url: String,
+    file: Uint8Array,
+    format: String,
+    lsb_deep: u8,
+) -> Result<js_sys::Array, JsValue> {
+    let mut client = new_client(&server_url)?;
+    let (capacity_bytes, overhead_bytes) = client
+        .capacity(file.to_vec(), format, lsb_deep)
+        .await
+        .map_err(|err| JsValue::from_str(&err.to_string()))?;
+
+    let result = js_sys::Array::new();
+    result.push(&JsValue::from_f64(capacity_bytes as f64));
+    result.push(&JsValue::from_f64(overhead_bytes as f64));
+    Ok(result)
+}
+
+fn new_client(server_url: &str) -> Result<StegoWaveWasmClient, JsValue> {
+    let url = server_url
+        .parse()
+        .map_err(|_| JsValue::from_str("Invalid server URL"))?;
+
+    Ok(StegoWaveWasmClient::new(url))
+}
@@ -0,0 +1,152 @@
+use gloo_net::http::{Request, Response};
+use js_sys::{Array, Uint8Array};
+use serde::Deserialize;
+use stego_wave::error::StegoWaveClientError;
+use stego_wave::object::StegoWaveClient;
+use url::Url;
+use web_sys::{Blob, FormData};
+
+#[derive(Deserialize)]
+struct CapacityResponse {
+    capacity_bytes: usize,
+    overhead_bytes: usize,
+}
+
+fn convert_gloo_error(_err: gloo_net::Error) -> StegoWaveClientError {
+    StegoWaveClientError::RequestFailed
+}
+
+fn build_form(file: &[u8], fields: &[(&str, &str)]) -> Result<FormData, StegoWaveClientError> {
+    let form = FormData::new().map_err(|_| StegoWaveClientError::RequestFailed)?;
+
+    let blob_parts = Array::new();
+    blob_parts.push(&Uint8Array::from(file));
+    let blob = Blob::new_with_u8_array_sequence(&blob_parts)
+        .map_err(|_| StegoWaveClientError::RequestFailed)?;
+    form.append_with_blob("file", &blob)
+        .map_err(|_| StegoWaveClientError::RequestFailed)?;
+
+    for (key, value) in fields {
+        form.append_with_str(key, value)
+            .map_err(|_| StegoWaveClientError::RequestFailed)?;
+    }
+
+    Ok(form)
+}
+
+#[derive(Clone)]
+pub struct StegoWaveWasmClient {
+    rest_url: Url,
+}
+
+impl StegoWaveWasmClient {
+    pub fn new(url: impl Into<Url>) -> Self {
+        Self {
+            rest_url: url.into(),
+        }
+    }
+
+    async fn post_form(&self, path: &str, form: FormData) -> Result<Response, StegoWaveClientError> {
+        let url = self
+            .rest_url
+            .join(path)
+            .map_err(|err| StegoWaveClientError::UlrInvalid(err.to_string()))?;
+
+        let response = Request::post(url.as_str())
+            .body(form)
+            .map_err(|_| StegoWaveClientError::RequestFailed)?
+            .send()
+            .await
+            .map_err(convert_gloo_error)?;
+
+        if !response.ok() {
+            let err_text = response.text().await.unwrap_or_default();
+            return Err(StegoWaveClientError::Response(err_text));
+        }
+
+        Ok(response)
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl StegoWaveClient for StegoWaveWasmClient {
+    async fn hide_message(
+        &mut self,
+        file: Vec<u8>,
+        message: String,
+        password: String,
+        format: String,
+        lsb_deep: u8,
+        compress: bool,
+        encrypt: bool,
+    ) -> Result<Vec<u8>, StegoWaveClientError> {
+        let form = build_form(
+            &file,
+            &[
+                ("message", &message),
+                ("password", &password),
+                ("format", &format),
+                ("lsb_deep", &lsb_deep.to_string()),
+                ("compress", &compress.to_string()),
+                ("encrypt", &encrypt.to_string()),
+            ],
+        )?;
+
+        let response = self.post_form("api/hide_message", form).await?;
+        response.binary().await.map_err(convert_gloo_error)
+    }
+
+    async fn extract_message(
+        &mut self,
+        file: Vec<u8>,
+        password: String,
+        format: String,
+        lsb_deep: u8,
+    ) -> Result<String, StegoWaveClientError> {
+        let form = build_form(
+            &file,
+            &[
+                ("password", &password),
+                ("format", &format),
+                ("lsb_deep", &lsb_deep.to_string()),
+            ],
+        )?;
+
+        let response = self.post_form("api/extract_message", form).await?;
+        response.text().await.map_err(convert_gloo_error)
+    }
+
+    async fn clear_message(
+        &mut self,
+        file: Vec<u8>,
+        password: String,
+        format: String,
+        lsb_deep: u8,
+    ) -> Result<Vec<u8>, StegoWaveClientError> {
+        let form = build_form(
+            &file,
+            &[
+                ("password", &password),
+                ("format", &format),
+                ("lsb_deep", &lsb_deep.to_string()),
+            ],
+        )?;
+
+        let response = self.post_form("api/clear_message", form).await?;
+        response.binary().await.map_err(convert_gloo_error)
+    }
+
+    async fn capacity(
+        &mut self,
+        file: Vec<u8>,
+        format: String,
+        lsb_deep: u8,
+    ) -> Result<(usize, usize), StegoWaveClientError> {
+        let form = build_form(&file, &[("format", &format), ("lsb_deep", &lsb_deep.to_string())])?;
+
+        let response = self.post_form("api/capacity", form).await?;
+        let capacity: CapacityResponse = response.json().await.map_err(convert_gloo_error)?;
+
+        Ok((capacity.capacity_bytes, capacity.overhead_bytes))
+    }
+}
@@ -0,0 +1,3 @@
+mod ws_client;
+
+pub use ws_client::StegoWaveWsClient;
@@ -0,0 +1,175 @@
+use futures::{SinkExt, StreamExt};
+use stego_wave::command::CommandRequest;
+use stego_wave::error::StegoWaveClientError;
+use stego_wave::object::StegoWaveClient;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+use url::Url;
+
+/// Size, in bytes, of the chunks a file is split into before streaming it over
+/// the socket, mirroring `StegoWaveGrpcClient`'s `create_by_chunk` semantics.
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+fn convert_tungstenite_error(_err: tokio_tungstenite::tungstenite::Error) -> StegoWaveClientError {
+    StegoWaveClientError::ConnectionFailed
+}
+
+/// What came back over the socket: a binary audio file (`Hide`/`Clear`) or a
+/// text message (`Extract`, or an error — the server answers both the same way).
+enum WsOutcome {
+    Audio(Vec<u8>),
+    Message(String),
+}
+
+#[derive(Clone)]
+pub struct StegoWaveWsClient {
+    ws_url: Url,
+}
+
+impl StegoWaveWsClient {
+    pub async fn new(url: impl Into<Url> + Send) -> Result<Self, StegoWaveClientError> {
+        Ok(Self { ws_url: url.into() })
+    }
+
+    /// Connects to `/ws/stego`, sends `control` as a single JSON text frame followed
+    /// by `file` split into `CHUNK_SIZE` binary frames, then closes the send side to
+    /// tell the server (`rest_service::api::websocket::stego_ws`) no more chunks are
+    /// coming, and waits for the single reply frame it answers with.
+    async fn run_command(
+        &self,
+        control: CommandRequest,
+        file: Vec<u8>,
+    ) -> Result<WsOutcome, StegoWaveClientError> {
+        let (ws_stream, _) = connect_async(self.ws_url.as_str())
+            .await
+            .map_err(|_err| StegoWaveClientError::ConnectionFailed)?;
+        let (mut write, mut read) = ws_stream.split();
+
+        let control_json = serde_json::to_string(&control)
+            .map_err(|err| StegoWaveClientError::Response(err.to_string()))?;
+        write
+            .send(Message::Text(control_json.into()))
+            .await
+            .map_err(convert_tungstenite_error)?;
+
+        let mut chunks = file.chunks(CHUNK_SIZE).peekable();
+        if chunks.peek().is_none() {
+            write
+                .send(Message::Binary(Vec::new().into()))
+                .await
+                .map_err(convert_tungstenite_error)?;
+        } else {
+            for chunk in chunks {
+                write
+                    .send(Message::Binary(chunk.to_vec().into()))
+                    .await
+                    .map_err(convert_tungstenite_error)?;
+            }
+        }
+        write
+            .send(Message::Close(None))
+            .await
+            .map_err(convert_tungstenite_error)?;
+
+        let mut audio = Vec::new();
+        let mut text_response = None;
+
+        while let Some(message) = read.next().await {
+            match message.map_err(convert_tungstenite_error)? {
+                Message::Binary(bytes) => audio.extend_from_slice(&bytes),
+                Message::Text(text) => text_response = Some(text.to_string()),
+                Message::Close(_) => break,
+                _ => {}
+            }
+        }
+
+        if let Some(text) = text_response {
+            Ok(WsOutcome::Message(text))
+        } else {
+            Ok(WsOutcome::Audio(audio))
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl StegoWaveClient for StegoWaveWsClient {
+    async fn hide_message(
+        &mut self,
+        file: Vec<u8>,
+        message: String,
+        password: String,
+        format: String,
+        lsb_deep: u8,
+        compress: bool,
+        encrypt: bool,
+    ) -> Result<Vec<u8>, StegoWaveClientError> {
+        let control = CommandRequest::Hide {
+            message,
+            password,
+            format,
+            lsb_deep,
+            compress,
+            encrypt,
+        };
+
+        match self.run_command(control, file).await? {
+            WsOutcome::Audio(bytes) => Ok(bytes),
+            WsOutcome::Message(text) => Err(StegoWaveClientError::Response(text)),
+        }
+    }
+
+    async fn extract_message(
+        &mut self,
+        file: Vec<u8>,
+        password: String,
+        format: String,
+        lsb_deep: u8,
+    ) -> Result<String, StegoWaveClientError> {
+        let control = CommandRequest::Extract {
+            password,
+            format,
+            lsb_deep,
+        };
+
+        match self.run_command(control, file).await? {
+            WsOutcome::Message(text) => Ok(text),
+            WsOutcome::Audio(_) => Err(StegoWaveClientError::Response(
+                "Unexpected binary response for extract_message".to_string(),
+            )),
+        }
+    }
+
+    async fn clear_message(
+        &mut self,
+        file: Vec<u8>,
+        password: String,
+        format: String,
+        lsb_deep: u8,
+    ) -> Result<Vec<u8>, StegoWaveClientError> {
+        let control = CommandRequest::Clear {
+            password,
+            format,
+            lsb_deep,
+        };
+
+        match self.run_command(control, file).await? {
+            WsOutcome::Audio(bytes) => Ok(bytes),
+            WsOutcome::Message(text) => Err(StegoWaveClientError::Response(text)),
+        }
+    }
+
+    // `stego_ws` only understands `CommandRequest::{Hide,Extract,Clear}` (it runs
+    // each connection through `stego_wave::command::execute`, which has no capacity
+    // variant), so there's no control frame this client could send for it. Route
+    // capacity lookups through the gRPC or REST client instead.
+    async fn capacity(
+        &mut self,
+        _file: Vec<u8>,
+        _format: String,
+        _lsb_deep: u8,
+    ) -> Result<(usize, usize), StegoWaveClientError> {
+        Err(StegoWaveClientError::Response(
+            "capacity is not supported over the WebSocket gateway".to_string(),
+        ))
+    }
+}